@@ -1,14 +1,21 @@
 module RustyObjectStore
 
-export init_object_store, get_object!, put_object, delete_object
-export StaticConfig, ClientOptions, Config, AzureConfig, AWSConfig
+export init_object_store, object_store_started, get_object!, get_object, put_object, delete_object
+export get_object_to_file, put_object_from_file, copy_object, signed_url
+export put_object_with_result, PutResult, get_object_range!, get_object_ranges!, get_objects!, get_object_from_url!
+export list_multipart_uploads, abort_stale_uploads, MultipartUpload
+export list_objects, prefix_size, ObjectMeta, last_modified_datetime, check_connection, head_object, head_objects, object_size, get_object_tags
+export StaticConfig, ClientOptions, Config, AzureConfig, AWSConfig, register_config
 export status_code, is_connection, is_timeout, is_early_eof, is_unknown, is_parse_url
+export is_permission_denied, is_precondition_failed, is_already_exists, is_checksum_mismatch
+export is_not_found, is_not_supported, is_cancelled, is_throttled, is_buffer_too_small, required_buffer_size
 export get_object_stream, ReadStream, finish!
 export put_object_stream, WriteStream, cancel!, shutdown!
-export current_metrics
+export warmup_connections, current_metrics, with_request_tag, with_max_retries, native_library_version, cancel_requests, connection_info
 
 using Base.Libc.Libdl: dlext
 using Base: @kwdef, @lock
+using Dates: DateTime, Millisecond
 using DocStringExtensions
 using object_store_ffi_jll
 using JSON3
@@ -52,12 +59,74 @@ $TYPEDFIELDS
     cache_tti_secs::Culonglong
     "Put requests with a size in bytes greater than this will use multipart operations"
     multipart_put_threshold::Culonglong
+    """
+    The size in bytes for each part of multipart put operations.
+    Backends may require a minimum part size, e.g. 5MB for S3 (except for the last part).
+    """
+    multipart_put_part_size::Culonglong = 10 * 1024 * 1024
+    """
+    The timeout in seconds for uploading each part of multipart put operations, after which the
+    part is retried without restarting the whole upload, a value of zero applies no per-part timeout.
+    """
+    multipart_put_part_timeout_secs::Culonglong = 0
+    """
+    The max number of parts of each multipart put operation that are uploaded concurrently,
+    a value of zero uses the native library default.
+    """
+    multipart_put_concurrency::Culonglong = 0
     "Get requests with a size in bytes greater than this will use multipart operations"
     multipart_get_threshold::Culonglong
     "The size in bytes for each part of multipart get operations"
     multipart_get_part_size::Culonglong
+    "Send all get requests as a single request, regardless of their size"
+    disable_multipart_get::Bool = false
+    """
+    The maximum size in bytes of a response chunk held in memory by single part get operations
+    before it is copied into the destination buffer, a value of zero uses chunks as received.
+    """
+    get_response_buffer_size::Culonglong = 0
+    """
+    The maximum size in bytes of the buffer of a get or put request, larger requests are rejected
+    with an error before being enqueued, a value of zero applies no limit.
+    """
+    max_request_bytes::Culonglong = 0
+    """
+    The maximum size in bytes of an object fetched by get operations, larger objects are rejected
+    with an error before any data is fetched, a value of zero applies no limit.
+    """
+    max_get_object_bytes::Culonglong = 0
+    """
+    The maximum size in bytes of the data buffered in memory by all concurrent requests, requests
+    wait for their size to be available before transferring data, a value of zero applies no limit.
+    """
+    max_total_buffer_bytes::Culonglong = 0
     "The max number of allowed Rust request tasks"
     concurrency_limit::Cuint
+    """
+    The min number of allowed Rust request tasks when adapting the concurrency to the observed latency
+    and error rates, between this and `concurrency_limit`. A value of zero disables the adaptive concurrency.
+    """
+    adaptive_concurrency_min::Cuint = 0
+    """
+    The time in milliseconds a request waits for space in the native request queue when it is full,
+    before the request is rejected and retried from Julia, a value of zero rejects immediately.
+    """
+    enqueue_timeout_ms::Culonglong = 0
+    """
+    The number of times the native library retries to enqueue a request, after a short jittered sleep,
+    before the request is rejected and retried from Julia, a value of zero rejects immediately.
+    """
+    enqueue_retries::Culonglong = 0
+    """
+    The time window in microseconds during which completed requests are coalesced into a single
+    notification of the Julia tasks waiting on them, a value of zero notifies each request on completion.
+    """
+    notify_batch_window_us::Culonglong = 0
+    """
+    The maximum time in seconds to build a client for a new configuration (e.g. when fetching credentials
+    from instance metadata), a value of zero disables the timeout. Failed attempts are not cached.
+    """
+    connection_build_timeout_secs::Culonglong = 0
 end
 
 function Base.show(io::IO, config::StaticConfig)
@@ -67,8 +136,22 @@ function Base.show(io::IO, config::StaticConfig)
     print(io, "cache_ttl_secs=", Int(config.cache_ttl_secs), ",")
     print(io, "cache_tti_secs=", Int(config.cache_tti_secs), ",")
     print(io, "multipart_put_threshold=", Int(config.multipart_put_threshold), ",")
+    print(io, "multipart_put_part_size=", Int(config.multipart_put_part_size), ",")
+    print(io, "multipart_put_part_timeout_secs=", Int(config.multipart_put_part_timeout_secs), ",")
+    print(io, "multipart_put_concurrency=", Int(config.multipart_put_concurrency), ",")
     print(io, "multipart_get_threshold=", Int(config.multipart_get_threshold), ",")
-    print(io, "multipart_get_part_size=", Int(config.multipart_get_part_size), ")")
+    print(io, "multipart_get_part_size=", Int(config.multipart_get_part_size), ",")
+    print(io, "disable_multipart_get=", config.disable_multipart_get, ",")
+    print(io, "get_response_buffer_size=", Int(config.get_response_buffer_size), ",")
+    print(io, "max_request_bytes=", Int(config.max_request_bytes), ",")
+    print(io, "max_get_object_bytes=", Int(config.max_get_object_bytes), ",")
+    print(io, "max_total_buffer_bytes=", Int(config.max_total_buffer_bytes), ",")
+    print(io, "concurrency_limit=", Int(config.concurrency_limit), ",")
+    print(io, "adaptive_concurrency_min=", Int(config.adaptive_concurrency_min), ",")
+    print(io, "enqueue_timeout_ms=", Int(config.enqueue_timeout_ms), ",")
+    print(io, "enqueue_retries=", Int(config.enqueue_retries), ",")
+    print(io, "notify_batch_window_us=", Int(config.notify_batch_window_us), ",")
+    print(io, "connection_build_timeout_secs=", Int(config.connection_build_timeout_secs), ")")
 end
 
 const DEFAULT_CONFIG = StaticConfig(
@@ -77,11 +160,42 @@ const DEFAULT_CONFIG = StaticConfig(
     cache_ttl_secs=30 * 60,
     cache_tti_secs=5 * 60,
     multipart_put_threshold=10 * 1024 * 1024,
+    multipart_put_part_size=10 * 1024 * 1024,
     multipart_get_threshold=8 * 1024 * 1024,
     multipart_get_part_size=8 * 1024 * 1024,
     concurrency_limit=512
 )
 
+# Values that the native library would accept but that would make every request fail or hang,
+# e.g. a zero concurrency limit deadlocks the request queue, so they are rejected up front.
+function invalid_config_message(config::StaticConfig)
+    config.concurrency_limit == 0 && return "concurrency_limit must be positive"
+    config.multipart_put_part_size == 0 && return "multipart_put_part_size must be positive"
+    config.multipart_get_part_size == 0 && !config.disable_multipart_get &&
+        return "multipart_get_part_size must be positive unless disable_multipart_get is set"
+    config.adaptive_concurrency_min > config.concurrency_limit &&
+        return "adaptive_concurrency_min must not be greater than concurrency_limit"
+    config.max_total_buffer_bytes != 0 && config.max_request_bytes > config.max_total_buffer_bytes &&
+        return "max_request_bytes must not be greater than max_total_buffer_bytes"
+    return nothing
+end
+
+# `StaticConfig` is passed by value to the native library, so its layout must match the one the library
+# was built with. Fields such as `adaptive_concurrency_min` or `max_total_buffer_bytes` only exist since
+# object_store_ffi 0.8, an older build (e.g. a local `OBJECT_STORE_LIB`) would misread them.
+const MIN_NATIVE_VERSION = v"0.8.0"
+
+function native_version_number()
+    # Builds older than 0.8 do not export the version symbol at all
+    version = try
+        native_library_version()
+    catch
+        return nothing
+    end
+    m = match(r"^object_store_ffi (\S+)", version)
+    return isnothing(m) ? nothing : tryparse(VersionNumber, m[1])
+end
+
 function default_panic_hook()
     println("Rust thread panicked, exiting the process")
     exit(1)
@@ -112,6 +226,51 @@ Base.@ccallable function notify_result(event_ptr::Ptr{Nothing})::Cint
     return 0
 end
 
+# This is the callback that Rust calls instead of `notify_result` to notify many Julia tasks at once,
+# when completions are batched (see `StaticConfig` `notify_batch_window_us`).
+# The argument is an array of `count` handles, which is only valid for the duration of the call.
+Base.@ccallable function notify_results(event_ptrs::Ptr{Ptr{Nothing}}, count::Culonglong)::Cint
+    for i in 1:count
+        event = unsafe_pointer_to_objref(unsafe_load(event_ptrs, i))::Base.Event
+        notify(event)
+    end
+    return 0
+end
+
+# This is the callback that Rust calls to report the progress of a transfer.
+# The argument is the progress handle passed from Julia in the @ccall, a pointer to a Channel that
+# forwards the progress to the task running the user callback, so Rust threads never block on it.
+# The channel is closed once the request returns, so late updates (e.g. after a cancellation) are
+# dropped, returning non-zero so that Rust stops reporting progress for the request.
+Base.@ccallable function notify_progress(channel_ptr::Ptr{Nothing}, transferred::Culonglong, total::Culonglong)::Cint
+    channel = unsafe_pointer_to_objref(channel_ptr)::Channel{Tuple{Int,Int}}
+    isopen(channel) || return 1
+    try
+        put!(channel, (Int(transferred), Int(total)))
+    catch e
+        # The channel can still be closed concurrently between the check and the put
+        e isa InvalidStateException && return 1
+        rethrow()
+    end
+    return 0
+end
+
+# Runs `f` with a progress handle for the native library, calling `progress(transferred, total)`
+# on a separate task for each update. The handle is null if no callback is given.
+function with_progress(f, progress)
+    isnothing(progress) && return f(C_NULL)
+    channel = Channel{Tuple{Int,Int}}(Inf)
+    consumer = @async for (transferred, total) in channel
+        progress(transferred, total)
+    end
+    try
+        return GC.@preserve channel f(pointer_from_objref(channel))
+    finally
+        close(channel)
+        wait(consumer)
+    end
+end
+
 # A dict of all tasks that are waiting some result from Rust
 # and should thus not be garbage collected.
 # This copies the behavior of Base.preserve_handle.
@@ -154,7 +313,8 @@ An optional panic hook may be provided to react to panics on Rust's native threa
 The default behavior is to log and exit the process.
 
 # Throws
-- `InitException`: if the runtime fails to start.
+- `InitException`: if the runtime fails to start, if the config is invalid,
+  e.g. with a zero `concurrency_limit`, or if the loaded native library is older than object_store_ffi 0.8.
 """
 function init_object_store(
     config::StaticConfig=DEFAULT_CONFIG;
@@ -165,10 +325,30 @@ function init_object_store(
         if _OBJECT_STORE_STARTED[]
             return nothing
         end
+        msg = invalid_config_message(config)
+        if !isnothing(msg)
+            throw(InitException("Invalid object store config: $(msg).", -1))
+        end
+        native_version = native_version_number()
+        if isnothing(native_version) || native_version < MIN_NATIVE_VERSION
+            throw(InitException(
+                "Unsupported native library $(something(native_version, "(unknown version)")), " *
+                "object_store_ffi $(MIN_NATIVE_VERSION) or newer is required.",
+                -1
+            ))
+        end
         _PANIC_HOOK = on_rust_panic
         panic_fn_ptr = @cfunction(panic_hook_wrapper, Cint, ())
         fn_ptr = @cfunction(notify_result, Cint, (Ptr{Nothing},))
-        res = @ccall rust_lib.start(config::StaticConfig, panic_fn_ptr::Ptr{Nothing}, fn_ptr::Ptr{Nothing})::Cint
+        batch_fn_ptr = @cfunction(notify_results, Cint, (Ptr{Ptr{Nothing}}, Culonglong))
+        progress_fn_ptr = @cfunction(notify_progress, Cint, (Ptr{Nothing}, Culonglong, Culonglong))
+        res = @ccall rust_lib.start(
+            config::StaticConfig,
+            panic_fn_ptr::Ptr{Nothing},
+            fn_ptr::Ptr{Nothing},
+            batch_fn_ptr::Ptr{Nothing},
+            progress_fn_ptr::Ptr{Nothing}
+        )::Cint
         if res != 0
             throw(InitException("Failed to initialise object store runtime.", res))
         end
@@ -177,12 +357,33 @@ function init_object_store(
     return nothing
 end
 
+"""
+    object_store_started() -> Bool
+
+Check whether the object store runtime is fully initialised and ready to accept requests,
+e.g. to wait for readiness when `init_object_store` is called by another task during startup.
+"""
+function object_store_started()
+    return (@ccall rust_lib.is_started()::Cint) == 0
+end
+
 macro option_print(obj, name, hide = false)
     return esc(:( !isnothing($obj.$name)
         && print(io, ", ", $(string(name)), "=", $hide ? "*****" : repr($obj.$name)) ))
 end
 
+# The native library reports a buffer that is too small with a distinct result (5),
+# and the buffer size required to hold the object in the response length.
+function buffer_too_small_exception(response, operation)
+    msg = response_error_to_string(response, operation)
+    return GetException(msg, BufferTooSmallError(Int(response.length)))
+end
+
 function response_error_to_string(response, operation)
+    if response.result == 3
+        # Requests cancelled with `cancel_requests` carry no error message
+        return string("failed to process ", operation, " with error: Request cancelled")
+    end
     err = string("failed to process ", operation, " with error: ", unsafe_string(response.error_message))
     @ccall rust_lib.destroy_cstring(response.error_message::Ptr{Cchar})::Cint
     return err
@@ -193,7 +394,15 @@ macro throw_on_error(response, operation, exception)
 end
 
 function throw_on_error(response, operation, exception)
-    return :( $(esc(:($response.result == 1))) ? throw($exception($response_error_to_string($(esc(response)), $operation))) : $(nothing) )
+    # 1 is a generic error, 3 a cancelled request and 4 a request rejected due to throttling,
+    # while 5 is a get into a buffer that is too small for the object
+    return quote
+        if $(esc(response)).result == 5
+            throw($buffer_too_small_exception($(esc(response)), $operation))
+        elseif $(esc(:($response.result == 1 || $response.result == 3 || $response.result == 4)))
+            throw($exception($response_error_to_string($(esc(response)), $operation), $(esc(response)).http_status))
+        end
+    end
 end
 
 function ensure_wait(event::Base.Event)
@@ -228,11 +437,47 @@ end
 # Keyword Arguments
 - `request_timeout_secs::Option{Int}`: (Optional) Client request timeout in seconds.
 - `connect_timeout_secs::Option{Int}`: (Optional) Client connection timeout in seconds.
+- `operation_deadline_secs::Option{Int}`: (Optional) Hard upper bound on the total duration of an operation
+  (in seconds), including all attempts, retries, backoff and copying of data. Unlike `retry_timeout_secs`,
+  which only stops further retries, the operation fails once the deadline is exceeded, and `is_timeout`
+  is true for the exception.
 - `max_retries::Option{Int}`: (Optional) Maximum number of retry attempts.
 - `retry_timeout_secs::Option{Int}`: (Optional) Maximum amount of time from the initial request after which no further retries will be attempted (in seconds).
 - `initial_backoff_ms::Option{Int}`: (Optional) Initial delay for exponential backoff (in milliseconds).
 - `max_backoff_ms::Option{Int}`: (Optional) Maximum delay for exponential backoff (in milliseconds).
 - `backoff_exp_base::Option{Float64}`: (Optional) The base of the exponential for backoff delay calculations.
+- `retry_on_connection_errors::Option{Bool}`: (Optional) Whether connection-level failures (e.g. connection refused or reset)
+  are retried, independently of server error responses. Client errors (4xx) are never retried.
+- `pool_idle_timeout_secs::Option{Int}`: (Optional) Time an idle HTTP connection is kept in the pool before being closed (in seconds).
+- `pool_max_idle_per_host::Option{Int}`: (Optional) Maximum number of idle HTTP connections kept in the pool per host.
+- `http2_keep_alive_interval_secs::Option{Int}`: (Optional) Interval between HTTP/2 keep-alive pings (in seconds).
+- `http2_keep_alive_timeout_secs::Option{Int}`: (Optional) Time to wait for a HTTP/2 keep-alive ping to be acknowledged before closing the connection (in seconds).
+- `http2_keep_alive_while_idle::Option{Bool}`: (Optional) Send HTTP/2 keep-alive pings even when there are no in-flight requests.
+- `allow_invalid_certificates::Option{Bool}`: (Optional) Skip TLS certificate validation.
+  This is insecure and is never enabled implicitly, not even when an alternative `host` is configured.
+- `ca_cert_pem::Option{String}`: (Optional) PEM encoded CA certificates to trust in addition to the system ones,
+  e.g. for internal endpoints signed by a private CA. This is the secure alternative to `allow_invalid_certificates`.
+- `allow_http::Option{Bool}`: (Optional) Allow plaintext HTTP connections, e.g. to an S3-compatible store
+  on a trusted network that does not serve HTTPS. By default this is only allowed for an alternative `host`
+  with an `http://` URL, such as emulators.
+- `user_agent::Option{String}`: (Optional) The User-Agent header sent with every request,
+  e.g. to identify the calling service in server-side request logs.
+  Defaults to the one of the native library, which identifies object_store_ffi and its version.
+- `cache_client::Option{Bool}`: (Optional) Set to `false` to build a client for each request and drop it afterwards,
+  instead of inserting it into the client cache. This avoids evicting frequently used clients when doing
+  one-off operations against many distinct accounts.
+- `max_requests_per_sec::Option{Int}`: (Optional) Limit the rate of requests sent with this configuration,
+  to stay within the throttling limits of the backend. Requests over the limit wait for their turn
+  (within the request timeout) instead of being sent and rejected with 429 or 503 responses.
+- `dns_overrides::Option{Dict{String, String}}`: (Optional) Resolve the given host names to the given IP addresses
+  instead of using the system DNS, e.g. `Dict("myaccount.blob.core.windows.net" => "10.0.0.5")` to pin the
+  storage host to an internal endpoint. TLS certificates are still validated against the host name.
+- `retry_error_kinds::Option{Vector{Symbol}}`: (Optional) Classes of errors reported by the backend to retry
+  on top of the transport-level retries, e.g. `[:not_found]` to wait out eventual consistency right after
+  a write. One or more of `:not_found`, `:precondition`, `:already_exists` and `:generic`.
+  Permission errors are never retried. Requires `error_retries`.
+- `error_retries::Option{Int}`: (Optional) The max number of retries of the `retry_error_kinds` errors,
+  with the same backoff as transport-level retries.
 """
 struct ClientOptions
     request_timeout_secs::Option{Int}
@@ -242,6 +487,22 @@ struct ClientOptions
     initial_backoff_ms::Option{Int}
     max_backoff_ms::Option{Int}
     backoff_exp_base::Option{Float64}
+    retry_on_connection_errors::Option{Bool}
+    pool_idle_timeout_secs::Option{Int}
+    pool_max_idle_per_host::Option{Int}
+    http2_keep_alive_interval_secs::Option{Int}
+    http2_keep_alive_timeout_secs::Option{Int}
+    http2_keep_alive_while_idle::Option{Bool}
+    allow_invalid_certificates::Option{Bool}
+    ca_cert_pem::Option{String}
+    allow_http::Option{Bool}
+    user_agent::Option{String}
+    cache_client::Option{Bool}
+    max_requests_per_sec::Option{Int}
+    operation_deadline_secs::Option{Int}
+    dns_overrides::Option{Dict{String, String}}
+    retry_error_kinds::Option{Vector{Symbol}}
+    error_retries::Option{Int}
     params::Dict{String, String}
 
     function ClientOptions(;
@@ -252,6 +513,22 @@ struct ClientOptions
         initial_backoff_ms::Option{Int} = nothing,
         max_backoff_ms::Option{Int} = nothing,
         backoff_exp_base::Option{Float64} = nothing,
+        retry_on_connection_errors::Option{Bool} = nothing,
+        pool_idle_timeout_secs::Option{Int} = nothing,
+        pool_max_idle_per_host::Option{Int} = nothing,
+        http2_keep_alive_interval_secs::Option{Int} = nothing,
+        http2_keep_alive_timeout_secs::Option{Int} = nothing,
+        http2_keep_alive_while_idle::Option{Bool} = nothing,
+        allow_invalid_certificates::Option{Bool} = nothing,
+        ca_cert_pem::Option{String} = nothing,
+        allow_http::Option{Bool} = nothing,
+        user_agent::Option{String} = nothing,
+        cache_client::Option{Bool} = nothing,
+        max_requests_per_sec::Option{Int} = nothing,
+        operation_deadline_secs::Option{Int} = nothing,
+        dns_overrides::Option{Dict{String, String}} = nothing,
+        retry_error_kinds::Option{Vector{Symbol}} = nothing,
+        error_retries::Option{Int} = nothing,
     )
         params = Dict()
         if !isnothing(request_timeout_secs)
@@ -287,6 +564,84 @@ struct ClientOptions
             params["backoff_exp_base"] = string(backoff_exp_base)
         end
 
+        if !isnothing(retry_on_connection_errors)
+            params["retry_on_connection_errors"] = string(retry_on_connection_errors)
+        end
+
+        if !isnothing(pool_idle_timeout_secs)
+            # Include `s` so parsing on Rust understands this as seconds
+            params["pool_idle_timeout"] = string(pool_idle_timeout_secs, "s")
+        end
+
+        if !isnothing(pool_max_idle_per_host)
+            params["pool_max_idle_per_host"] = string(pool_max_idle_per_host)
+        end
+
+        if !isnothing(http2_keep_alive_interval_secs)
+            # Include `s` so parsing on Rust understands this as seconds
+            params["http2_keep_alive_interval"] = string(http2_keep_alive_interval_secs, "s")
+        end
+
+        if !isnothing(http2_keep_alive_timeout_secs)
+            # Include `s` so parsing on Rust understands this as seconds
+            params["http2_keep_alive_timeout"] = string(http2_keep_alive_timeout_secs, "s")
+        end
+
+        if !isnothing(http2_keep_alive_while_idle)
+            params["http2_keep_alive_while_idle"] = string(http2_keep_alive_while_idle)
+        end
+
+        if !isnothing(allow_invalid_certificates)
+            params["allow_invalid_certificates"] = string(allow_invalid_certificates)
+        end
+
+        if !isnothing(ca_cert_pem)
+            if !occursin("-----BEGIN CERTIFICATE-----", ca_cert_pem)
+                throw(ArgumentError("ca_cert_pem should contain PEM encoded certificates"))
+            end
+            params["ca_cert_pem"] = ca_cert_pem
+        end
+
+        if !isnothing(allow_http)
+            params["allow_http"] = string(allow_http)
+        end
+
+        if !isnothing(user_agent)
+            params["user_agent"] = user_agent
+        end
+
+        if !isnothing(cache_client)
+            params["cache_client"] = string(cache_client)
+        end
+
+        if !isnothing(max_requests_per_sec)
+            params["max_requests_per_sec"] = string(max_requests_per_sec)
+        end
+
+        if !isnothing(operation_deadline_secs)
+            # Include `s` so parsing on Rust understands this as seconds
+            params["operation_deadline"] = string(operation_deadline_secs, "s")
+        end
+
+        if !isnothing(dns_overrides)
+            # Serialized as a JSON object of host to IP address, as params only hold strings
+            params["dns_overrides"] = JSON3.write(dns_overrides)
+        end
+
+        if isnothing(retry_error_kinds) != isnothing(error_retries)
+            throw(ArgumentError("retry_error_kinds and error_retries should be provided together"))
+        end
+
+        if !isnothing(retry_error_kinds)
+            for kind in retry_error_kinds
+                if !(kind in (:not_found, :precondition, :already_exists, :generic))
+                    throw(ArgumentError("Unsupported retry error kind: $(repr(kind))"))
+                end
+            end
+            params["retry_error_kinds"] = join(sort(unique(retry_error_kinds)), ",")
+            params["error_retries"] = string(error_retries)
+        end
+
         return new(
             request_timeout_secs,
             connect_timeout_secs,
@@ -295,6 +650,22 @@ struct ClientOptions
             initial_backoff_ms,
             max_backoff_ms,
             backoff_exp_base,
+            retry_on_connection_errors,
+            pool_idle_timeout_secs,
+            pool_max_idle_per_host,
+            http2_keep_alive_interval_secs,
+            http2_keep_alive_timeout_secs,
+            http2_keep_alive_while_idle,
+            allow_invalid_certificates,
+            ca_cert_pem,
+            allow_http,
+            user_agent,
+            cache_client,
+            max_requests_per_sec,
+            operation_deadline_secs,
+            dns_overrides,
+            retry_error_kinds,
+            error_retries,
             params
         )
     end
@@ -309,6 +680,23 @@ function Base.show(io::IO, opts::ClientOptions)
     @option_print(opts, initial_backoff_ms)
     @option_print(opts, max_backoff_ms)
     @option_print(opts, backoff_exp_base)
+    @option_print(opts, retry_on_connection_errors)
+    @option_print(opts, pool_idle_timeout_secs)
+    @option_print(opts, pool_max_idle_per_host)
+    @option_print(opts, http2_keep_alive_interval_secs)
+    @option_print(opts, http2_keep_alive_timeout_secs)
+    @option_print(opts, http2_keep_alive_while_idle)
+    @option_print(opts, allow_invalid_certificates)
+    # Certificates are long and not secret, so only their presence is shown
+    !isnothing(opts.ca_cert_pem) && print(io, ", ca_cert_pem=...")
+    @option_print(opts, allow_http)
+    @option_print(opts, user_agent)
+    @option_print(opts, cache_client)
+    @option_print(opts, max_requests_per_sec)
+    @option_print(opts, operation_deadline_secs)
+    @option_print(opts, dns_overrides)
+    @option_print(opts, retry_error_kinds)
+    @option_print(opts, error_retries)
     print(io, ")")
 end
 
@@ -334,9 +722,12 @@ struct Config <: AbstractConfig
     # For this to be effective the recommended usage pattern is to reuse this object often
     # instead of constructing for each use.
     config_string::String
+    # Id of the connection in the native registry, or zero if not registered (see `register_config`).
+    connection_id::UInt64
     function Config(url::String, params::Dict{String, String})
-        return new(url_params_to_config_string(url, params))
+        return new(url_params_to_config_string(url, params), 0)
     end
+    Config(config::Config, connection_id::UInt64) = new(config.config_string, connection_id)
 end
 
 function url_params_to_config_string(url::String, params::Dict{String, String})
@@ -346,6 +737,30 @@ end
 
 into_config(conf::Config) = conf
 
+"""
+    register_config(conf) -> Config
+
+Register the connection described by `conf` with the native library, returning an equivalent `Config`
+that refers to the connection by id.
+
+Requests using the returned config skip hashing the serialized configuration to find the cached client,
+which reduces overhead on hot paths with a fixed set of connections. The registration lasts for the
+lifetime of the process.
+
+# Arguments
+- `conf::AbstractConfig`: The configuration to register.
+
+# Throws
+- `ErrorException`: If the configuration cannot be registered.
+"""
+function register_config(conf::AbstractConfig)
+    config = into_config(conf)
+    config.connection_id != 0 && return config
+    id = GC.@preserve config @ccall rust_lib.register_connection(config::Ref{Config})::Culonglong
+    id == 0 && error("Failed to register connection for $(repr(config))")
+    return Config(config, UInt64(id))
+end
+
 function Base.show(io::IO, config::Config)
     dict = JSON3.read(config.config_string, Dict{String, String})
     print(io, "Config(")
@@ -359,15 +774,68 @@ function Base.show(io::IO, config::Config)
     print(io, ")")
 end
 
-const _ConfigFFI = Cstring
+struct _ConfigFFI
+    config_string::Cstring
+    # When non-zero the native library resolves the client by id, without hashing the config string.
+    connection_id::Culonglong
+    # Only used by the native library for tracing and metrics, it is not part of the client cache key
+    # and it is never sent to the object store.
+    request_tag::Cstring
+    # When non-negative, overrides the max retries of the client for this request only.
+    # Like the tag, it is not part of the client cache key.
+    max_retries::Clonglong
+end
+
+const _REQUEST_TAG_KEY = :RustyObjectStore_request_tag
+
+"""
+    with_request_tag(f, tag::String)
+
+Run `f()` attaching `tag` to all object store requests made by the current task,
+e.g. a job or partition id to correlate native logs and metrics with.
+
+The tag is task-local, so it is not inherited by tasks spawned within `f`.
+"""
+function with_request_tag(f, tag::String)
+    return task_local_storage(f, _REQUEST_TAG_KEY, tag)
+end
+
+request_tag() = get(task_local_storage(), _REQUEST_TAG_KEY, "")::String
+
+const _MAX_RETRIES_KEY = :RustyObjectStore_max_retries
+
+"""
+    with_max_retries(f, max_retries::Int)
+
+Run `f()` overriding the `ClientOptions` `max_retries` of all object store requests made by the
+current task, e.g. to retry an idempotent bulk operation more eagerly than the connection default.
+
+The override is applied by the native library to each request, so it reuses the cached client
+of the connection. Setting `max_retries` in the `ClientOptions` instead builds and caches a
+distinct client for each value, which can evict other clients from the cache.
+
+The override is task-local, so it is not inherited by tasks spawned within `f`.
+"""
+function with_max_retries(f, max_retries::Int)
+    max_retries < 0 && throw(ArgumentError("max_retries must be non-negative, got $(max_retries)"))
+    return task_local_storage(f, _MAX_RETRIES_KEY, max_retries)
+end
+
+max_retries_override() = get(task_local_storage(), _MAX_RETRIES_KEY, -1)::Int
 
 function Base.cconvert(::Type{Ref{Config}}, config::Config)
-   config_ffi = Base.unsafe_convert(Cstring, Base.cconvert(Cstring, config.config_string))::_ConfigFFI
+    tag = request_tag()
+    config_ffi = _ConfigFFI(
+        Base.unsafe_convert(Cstring, Base.cconvert(Cstring, config.config_string)),
+        config.connection_id,
+        Base.unsafe_convert(Cstring, Base.cconvert(Cstring, tag)),
+        max_retries_override()
+    )
     # cconvert ensures its outputs are preserved during a ccall, so we can crate a pointer
-    # safely in the unsafe_convert call.
-    return config_ffi, Ref(config_ffi)
+    # safely in the unsafe_convert call. The tag is included to keep it alive as well.
+    return tag, Ref(config_ffi)
 end
-function Base.unsafe_convert(::Type{Ref{Config}}, x::Tuple{T,Ref{T}}) where {T<:_ConfigFFI}
+function Base.unsafe_convert(::Type{Ref{Config}}, x::Tuple{String,Ref{_ConfigFFI}})
     return Base.unsafe_convert(Ptr{_ConfigFFI}, x[2])
 end
 
@@ -384,7 +852,24 @@ It is recommended to reuse an instance for many operations.
 - `container_name::String`: Azure container name.
 - `storage_account_key::Option{String}`: (Optional) Azure storage account key (conflicts with storage_sas_token).
 - `storage_sas_token::Option{String}`: (Optional) Azure storage SAS token (conflicts with storage_account_key).
+- `auth_mode::Symbol`: (Optional) How requests are authenticated, one of:
+  - `:default`: use `storage_account_key` or `storage_sas_token` if provided,
+    otherwise send unsigned requests (default).
+  - `:managed_identity`: fetch tokens from the instance metadata service (IMDS).
+    `client_id` selects a user-assigned identity.
+  - `:workload_identity`: exchange the token in `federated_token_file` for an access token,
+    e.g. when running in AKS with workload identity federation. Requires `client_id` and `tenant_id`.
+  - `:azure_cli`: use the credentials of the Azure CLI, intended for local development.
+- `client_id::Option{String}`: (Optional) Client id of the identity to authenticate as.
+- `tenant_id::Option{String}`: (Optional) Tenant id of the identity to authenticate as.
+- `federated_token_file::Option{String}`: (Optional) Path of the federated token file for `auth_mode=:workload_identity`.
 - `host::Option{String}`: (Optional) Alternative Azure host. For example, if using Azurite.
+  The URL may include a non-default port, and `storage_account_name` and `storage_account_key`
+  are used as given, so emulators configured with custom accounts are supported.
+  TLS certificates are still validated, see `ClientOptions` `allow_invalid_certificates`.
+- `path_prefix::Option{String}`: (Optional) A prefix joined to the paths of all requests, e.g. with
+  `path_prefix="partition=1"` the path `"data.csv"` refers to the object `"partition=1/data.csv"`.
+  Listed locations are relative to the prefix as well.
 - `opts::ClientOptions`: (Optional) Client configuration options.
 """
 struct AzureConfig <: AbstractConfig
@@ -392,7 +877,12 @@ struct AzureConfig <: AbstractConfig
     container_name::String
     storage_account_key::Option{String}
     storage_sas_token::Option{String}
+    auth_mode::Symbol
+    client_id::Option{String}
+    tenant_id::Option{String}
+    federated_token_file::Option{String}
     host::Option{String}
+    path_prefix::Option{String}
     opts::ClientOptions
     cached_config::Config
     function AzureConfig(;
@@ -400,13 +890,30 @@ struct AzureConfig <: AbstractConfig
         container_name::String,
         storage_account_key::Option{String} = nothing,
         storage_sas_token::Option{String} = nothing,
+        auth_mode::Symbol = :default,
+        client_id::Option{String} = nothing,
+        tenant_id::Option{String} = nothing,
+        federated_token_file::Option{String} = nothing,
         host::Option{String} = nothing,
+        path_prefix::Option{String} = nothing,
         opts::ClientOptions = ClientOptions()
     )
         if !isnothing(storage_account_key) && !isnothing(storage_sas_token)
             error("Should provide either a storage_account_key or a storage_sas_token")
         end
 
+        if !(auth_mode in (:default, :managed_identity, :workload_identity, :azure_cli))
+            throw(ArgumentError("Unsupported auth_mode: $(repr(auth_mode))"))
+        end
+
+        if auth_mode != :default && (!isnothing(storage_account_key) || !isnothing(storage_sas_token))
+            throw(ArgumentError("Credentials should not be provided when using auth_mode=$(repr(auth_mode))"))
+        end
+
+        if auth_mode == :workload_identity && (isnothing(client_id) || isnothing(tenant_id) || isnothing(federated_token_file))
+            throw(ArgumentError("auth_mode=:workload_identity requires client_id, tenant_id and federated_token_file"))
+        end
+
         params = copy(opts.params)
 
         params["azure_storage_account_name"] = storage_account_name
@@ -418,11 +925,32 @@ struct AzureConfig <: AbstractConfig
             params["azure_storage_sas_token"] = storage_sas_token
         end
 
+        if !isnothing(client_id)
+            params["azure_client_id"] = client_id
+        end
+
+        if !isnothing(tenant_id)
+            params["azure_tenant_id"] = tenant_id
+        end
+
+        if !isnothing(federated_token_file)
+            params["azure_federated_token_file"] = federated_token_file
+        end
+
+        if auth_mode == :azure_cli
+            params["azure_use_azure_cli"] = "true"
+        end
+
         if !isnothing(host)
             params["azurite_host"] = host
         end
 
-        if isnothing(storage_account_key) && isnothing(storage_sas_token)
+        if !isnothing(path_prefix)
+            params["path_prefix"] = path_prefix
+        end
+
+        # Without explicit credentials or another auth_mode, requests are sent unsigned (anonymous access)
+        if auth_mode == :default && isnothing(storage_account_key) && isnothing(storage_sas_token)
             params["azure_skip_signature"] = "true"
         end
 
@@ -433,7 +961,12 @@ struct AzureConfig <: AbstractConfig
             container_name,
             storage_account_key,
             storage_sas_token,
+            auth_mode,
+            client_id,
+            tenant_id,
+            federated_token_file,
             host,
+            path_prefix,
             opts,
             cached_config
         )
@@ -442,13 +975,40 @@ end
 
 into_config(conf::AzureConfig) = conf.cached_config
 
+"""
+    AzureConfig(conf::AzureConfig; container_name) -> AzureConfig
+
+Copy `conf` targeting another container of the same storage account, with the same credentials
+and client options.
+"""
+function AzureConfig(conf::AzureConfig; container_name::String)
+    return AzureConfig(;
+        storage_account_name=conf.storage_account_name,
+        container_name,
+        storage_account_key=conf.storage_account_key,
+        storage_sas_token=conf.storage_sas_token,
+        auth_mode=conf.auth_mode,
+        client_id=conf.client_id,
+        tenant_id=conf.tenant_id,
+        federated_token_file=conf.federated_token_file,
+        host=conf.host,
+        path_prefix=conf.path_prefix,
+        opts=conf.opts
+    )
+end
+
 function Base.show(io::IO, conf::AzureConfig)
     print(io, "AzureConfig("),
     print(io, "storage_account_name=", repr(conf.storage_account_name), ", ")
     print(io, "container_name=", repr(conf.container_name))
     @option_print(conf, storage_account_key, true)
     @option_print(conf, storage_sas_token, true)
+    conf.auth_mode != :default && print(io, ", auth_mode=", repr(conf.auth_mode))
+    @option_print(conf, client_id)
+    @option_print(conf, tenant_id)
+    @option_print(conf, federated_token_file)
     @option_print(conf, host)
+    @option_print(conf, path_prefix)
     print(io, ", ", "opts=", repr(conf.opts), ")")
 end
 
@@ -466,6 +1026,19 @@ It is recommended to reuse an instance for many operations.
 - `secret_access_key::Option{String}`: (Optional) AWS S3 secret access key.
 - `session_token::Option{String}`: (Optional) AWS S3 session_token.
 - `host::Option{String}`: (Optional) Alternative S3 host. For example, if using Minio.
+  TLS certificates are still validated, see `ClientOptions` `allow_invalid_certificates`.
+- `sse_algorithm::Option{String}`: (Optional) Server-side encryption applied to written objects,
+  one of `"AES256"`, `"aws:kms"` or `"aws:kms:dsse"`. Encrypted objects are decrypted
+  transparently on read.
+- `sse_kms_key_id::Option{String}`: (Optional) Id of the KMS key used to encrypt written objects.
+  Requires `sse_algorithm` to be `"aws:kms"` or `"aws:kms:dsse"`.
+- `copy_if_not_exists::Option{String}`: (Optional) How `copy_object` with `overwrite=false` is made atomic,
+  as S3 has no native support for it. For example `"header:<name>:<value>"` for S3-compatible stores
+  that support a conditional copy header, or `"multipart"` for stores that support conditional
+  multipart uploads. See the object_store documentation of `S3CopyIfNotExists` for all options.
+- `path_prefix::Option{String}`: (Optional) A prefix joined to the paths of all requests, e.g. with
+  `path_prefix="partition=1"` the path `"data.csv"` refers to the object `"partition=1/data.csv"`.
+  Listed locations are relative to the prefix as well.
 - `opts::ClientOptions`: (Optional) Client configuration options.
 """
 struct AWSConfig <: AbstractConfig
@@ -476,6 +1049,10 @@ struct AWSConfig <: AbstractConfig
     session_token::Option{String}
     use_instance_metadata::Bool
     host::Option{String}
+    sse_algorithm::Option{String}
+    sse_kms_key_id::Option{String}
+    copy_if_not_exists::Option{String}
+    path_prefix::Option{String}
     opts::ClientOptions
     cached_config::Config
     function AWSConfig(;
@@ -486,6 +1063,10 @@ struct AWSConfig <: AbstractConfig
         session_token::Option{String} = nothing,
         use_instance_metadata::Bool = false,
         host::Option{String} = nothing,
+        sse_algorithm::Option{String} = nothing,
+        sse_kms_key_id::Option{String} = nothing,
+        copy_if_not_exists::Option{String} = nothing,
+        path_prefix::Option{String} = nothing,
         opts::ClientOptions = ClientOptions()
     )
         params = copy(opts.params)
@@ -519,6 +1100,28 @@ struct AWSConfig <: AbstractConfig
             error("Credentials should not be provided when using instance metadata")
         end
 
+        if !isnothing(sse_algorithm)
+            if !(sse_algorithm in ("AES256", "aws:kms", "aws:kms:dsse"))
+                throw(ArgumentError("Unsupported sse_algorithm: $(repr(sse_algorithm))"))
+            end
+            params["aws_server_side_encryption"] = sse_algorithm
+        end
+
+        if !isnothing(sse_kms_key_id)
+            if !(sse_algorithm in ("aws:kms", "aws:kms:dsse"))
+                throw(ArgumentError("sse_kms_key_id requires sse_algorithm to be \"aws:kms\" or \"aws:kms:dsse\""))
+            end
+            params["aws_sse_kms_key_id"] = sse_kms_key_id
+        end
+
+        if !isnothing(copy_if_not_exists)
+            params["aws_copy_if_not_exists"] = copy_if_not_exists
+        end
+
+        if !isnothing(path_prefix)
+            params["path_prefix"] = path_prefix
+        end
+
         map!(v -> strip(v), values(params))
         cached_config = Config("s3://$(strip(bucket_name))/", params)
         return new(
@@ -529,6 +1132,10 @@ struct AWSConfig <: AbstractConfig
             session_token,
             use_instance_metadata,
             host,
+            sse_algorithm,
+            sse_kms_key_id,
+            copy_if_not_exists,
+            path_prefix,
             opts,
             cached_config
         )
@@ -537,6 +1144,28 @@ end
 
 into_config(conf::AWSConfig) = conf.cached_config
 
+"""
+    AWSConfig(conf::AWSConfig; bucket_name) -> AWSConfig
+
+Copy `conf` targeting another bucket, with the same credentials and client options.
+"""
+function AWSConfig(conf::AWSConfig; bucket_name::String)
+    return AWSConfig(;
+        region=conf.region,
+        bucket_name,
+        access_key_id=conf.access_key_id,
+        secret_access_key=conf.secret_access_key,
+        session_token=conf.session_token,
+        use_instance_metadata=conf.use_instance_metadata,
+        host=conf.host,
+        sse_algorithm=conf.sse_algorithm,
+        sse_kms_key_id=conf.sse_kms_key_id,
+        copy_if_not_exists=conf.copy_if_not_exists,
+        path_prefix=conf.path_prefix,
+        opts=conf.opts
+    )
+end
+
 function Base.show(io::IO, conf::AWSConfig)
     print(io, "AWSConfig("),
     print(io, "region=", repr(conf.region), ", ")
@@ -546,179 +1175,2164 @@ function Base.show(io::IO, conf::AWSConfig)
     @option_print(conf, session_token, true)
     conf.use_instance_metadata && print(io, "use_instance_metadata=", repr(conf.use_instance_metadata))
     @option_print(conf, host)
+    @option_print(conf, sse_algorithm)
+    @option_print(conf, sse_kms_key_id)
+    @option_print(conf, copy_if_not_exists)
+    @option_print(conf, path_prefix)
     print(io, ", ", "opts=", repr(conf.opts), ")")
 end
 
-mutable struct Response
-    result::Cint
-    length::Culonglong
-    error_message::Ptr{Cchar}
-    context::Ptr{Cvoid}
+mutable struct Response
+    result::Cint
+    length::Culonglong
+    http_status::Cushort
+    error_message::Ptr{Cchar}
+    context::Ptr{Cvoid}
+
+    Response() = new(-1, 0, 0, C_NULL, C_NULL)
+end
+
+mutable struct PutResponseFFI
+    result::Cint
+    length::Culonglong
+    e_tag::Ptr{Cchar}
+    version::Ptr{Cchar}
+    http_status::Cushort
+    error_message::Ptr{Cchar}
+    context::Ptr{Cvoid}
+
+    PutResponseFFI() = new(-1, 0, C_NULL, C_NULL, 0, C_NULL, C_NULL)
+end
+
+"""
+    $TYPEDEF
+
+Outcome of a successful put request.
+
+# Fields
+$TYPEDFIELDS
+"""
+struct PutResult
+    "The number of bytes written to the object store"
+    nbytes::Int
+    "The ETag assigned by the server to the written object, if reported"
+    e_tag::Option{String}
+    "The version id assigned by the server to the written object, if the backend is versioned"
+    version::Option{String}
+end
+
+# Takes ownership of a string allocated by the native library, returning `nothing` for null pointers.
+function take_cstring(ptr::Ptr{Cchar})
+    ptr == C_NULL && return nothing
+    str = unsafe_string(ptr)
+    @ccall rust_lib.destroy_cstring(ptr::Ptr{Cchar})::Cint
+    return str
+end
+
+abstract type ErrorReason end
+
+struct ConnectionError <: ErrorReason end
+struct StatusError <: ErrorReason
+    code::Int
+end
+struct EarlyEOF <: ErrorReason end
+struct TimeoutError <: ErrorReason end
+struct ParseURLError <: ErrorReason end
+struct PreconditionError <: ErrorReason end
+struct AlreadyExistsError <: ErrorReason end
+struct NotSupportedError <: ErrorReason end
+struct CancelledError <: ErrorReason end
+struct BufferTooSmallError <: ErrorReason
+    required_size::Int
+end
+struct UnknownError <: ErrorReason end
+
+abstract type RequestException <: Exception end
+struct GetException <: RequestException
+    msg::String
+    reason::ErrorReason
+
+    GetException(msg, http_status=0) = new(msg, rust_message_to_reason(msg, http_status))
+    GetException(msg, reason::ErrorReason) = new(msg, reason)
+end
+struct PutException <: RequestException
+    msg::String
+    reason::ErrorReason
+
+    PutException(msg, http_status=0) = new(msg, rust_message_to_reason(msg, http_status))
+end
+struct DeleteException <: RequestException
+    msg::String
+    reason::ErrorReason
+
+    DeleteException(msg, http_status=0) = new(msg, rust_message_to_reason(msg, http_status))
+end
+struct ListException <: RequestException
+    msg::String
+    reason::ErrorReason
+
+    ListException(msg, http_status=0) = new(msg, rust_message_to_reason(msg, http_status))
+end
+struct HeadException <: RequestException
+    msg::String
+    reason::ErrorReason
+
+    HeadException(msg, http_status=0) = new(msg, rust_message_to_reason(msg, http_status))
+end
+struct CopyException <: RequestException
+    msg::String
+    reason::ErrorReason
+
+    CopyException(msg, http_status=0) = new(msg, rust_message_to_reason(msg, http_status))
+end
+struct SignException <: RequestException
+    msg::String
+    reason::ErrorReason
+
+    SignException(msg, http_status=0) = new(msg, rust_message_to_reason(msg, http_status))
+end
+struct CheckConnectionException <: RequestException
+    msg::String
+    reason::ErrorReason
+
+    CheckConnectionException(msg, http_status=0) = new(msg, rust_message_to_reason(msg, http_status))
+end
+
+
+function reason(e::GetException)
+    return e.reason::ErrorReason
+end
+
+function reason(e::PutException)
+    return e.reason::ErrorReason
+end
+
+function reason(e::DeleteException)
+    return e.reason::ErrorReason
+end
+
+function reason(e::ListException)
+    return e.reason::ErrorReason
+end
+
+function reason(e::HeadException)
+    return e.reason::ErrorReason
+end
+
+function reason(e::CopyException)
+    return e.reason::ErrorReason
+end
+
+function reason(e::SignException)
+    return e.reason::ErrorReason
+end
+
+function reason(e::CheckConnectionException)
+    return e.reason::ErrorReason
+end
+
+function reason(e::Exception)
+    return UnknownError()
+end
+
+function status_code(e::Exception)
+    return reason(e) isa StatusError ? reason(e).code : nothing
+end
+
+function is_connection(e::Exception)
+    return reason(e) isa ConnectionError
+end
+
+function is_timeout(e::Exception)
+    return reason(e) isa TimeoutError
+end
+
+function is_early_eof(e::Exception)
+    return reason(e) isa EarlyEOF
+end
+
+function is_parse_url(e::Exception)
+    return reason(e) isa ParseURLError
+end
+
+function is_unknown(e::Exception)
+    return reason(e) isa UnknownError
+end
+
+# Authentication and authorization failures (e.g. expired SAS token or wrong key) are not
+# resolved by retrying the request, the caller should refresh credentials instead.
+function is_permission_denied(e::Exception)
+    code = status_code(e)
+    return code == 401 || code == 403
+end
+
+# A conditional request was rejected because the object changed, e.g. a put with `mode=:update`.
+function is_precondition_failed(e::Exception)
+    return reason(e) isa PreconditionError || status_code(e) == 412
+end
+
+# A create-only request was rejected because the object already exists, e.g. a put with `mode=:create`.
+function is_already_exists(e::Exception)
+    return reason(e) isa AlreadyExistsError
+end
+
+# The operation is not supported by the backend or its configuration, e.g. `copy_object` with `overwrite=false`.
+function is_not_supported(e::Exception)
+    return reason(e) isa NotSupportedError
+end
+
+# The object does not exist, e.g. a head or get of a path that was never written or was deleted.
+function is_not_found(e::Exception)
+    return status_code(e) == 404
+end
+
+# The backend is overloaded and rejected the request after all retries, so load should be shed.
+function is_throttled(e::Exception)
+    return status_code(e) in (429, 503)
+end
+
+# A get request was rejected because the buffer cannot hold the object, see `required_buffer_size`.
+function is_buffer_too_small(e::Exception)
+    return reason(e) isa BufferTooSmallError
+end
+
+# The buffer size needed by a get request that failed with `is_buffer_too_small`, so the caller can
+# reallocate and retry. This is the exact object size when known, otherwise a lower bound.
+function required_buffer_size(e::Exception)
+    return reason(e) isa BufferTooSmallError ? reason(e).required_size : nothing
+end
+
+# The request was cancelled with `cancel_requests`.
+function is_cancelled(e::Exception)
+    return reason(e) isa CancelledError
+end
+
+# The server rejected an upload because the data did not match its checksum, e.g. a put with `content_md5=true`.
+# Backends report it with a generic 400 status, so it is identified by the S3 or Azure error code.
+function is_checksum_mismatch(e::Exception)
+    return e isa RequestException && status_code(e) == 400 &&
+        (contains(e.msg, "BadDigest") || contains(e.msg, "Md5Mismatch"))
+end
+
+
+# The HTTP status reported by the native library, if any, takes precedence over the status parsed
+# from the message, which is not included in all error messages.
+# The `AlreadyExists` error of object_store, which includes the location of the object
+const ALREADY_EXISTS_REGEX = r"Object at location \S+ already exists: "
+
+function rust_message_to_reason(msg::AbstractString, http_status::Integer)
+    # A create-only request that conflicts with an existing object is reported with a different
+    # status by each backend (409 on Azure, 412 on S3), so the error variant is checked as well
+    if http_status in (409, 412) && contains(msg, ALREADY_EXISTS_REGEX)
+        return AlreadyExistsError()
+    end
+    reason = rust_message_to_reason(msg)
+    if http_status > 0 && (reason isa UnknownError || reason isa StatusError)
+        return StatusError(Int(http_status))
+    end
+    return reason
+end
+
+function rust_message_to_reason(msg::AbstractString)
+    if (
+        contains(msg, "connection error")
+        || contains(msg, "tcp connect error")
+        || contains(msg, "error trying to connect")
+        || contains(msg, "client error (Connect)")
+       ) && (
+        contains(msg, "deadline has elapsed")
+        || contains(msg, "Connection refused")
+        || contains(msg, "Connection reset by peer")
+        || contains(msg, "dns error")
+       )
+        return ConnectionError()
+    elseif contains(msg, "Client error with status")
+        m = match(r"Client error with status (\d+) ", msg)
+        if !isnothing(m)
+            code = tryparse(Int, m.captures[1])
+            if !isnothing(code)
+                return StatusError(code)
+            else
+                return UnknownError()
+            end
+        else
+            return UnknownError()
+        end
+    elseif contains(msg, "HTTP status server error")
+        m = match(r"HTTP status server error \((\d+) ", msg)
+        if !isnothing(m)
+            code = tryparse(Int, m.captures[1])
+            if !isnothing(code)
+                return StatusError(code)
+            else
+                return UnknownError()
+            end
+        else
+            return UnknownError()
+        end
+    elseif contains(msg, "connection closed before message completed") ||
+        contains(msg, "end of file before message length reached") ||
+        contains(msg, "Connection reset by peer")
+        return EarlyEOF()
+    elseif contains(msg, "timed out") || contains(msg, "Operation deadline exceeded")
+        return TimeoutError()
+    elseif contains(msg, "Unable to convert URL") ||
+        contains(msg, "Unable to recognise URL")
+        return ParseURLError()
+    elseif contains(msg, "Request precondition failure")
+        return PreconditionError()
+    elseif contains(msg, ALREADY_EXISTS_REGEX)
+        return AlreadyExistsError()
+    elseif contains(msg, "Request cancelled")
+        return CancelledError()
+    elseif contains(msg, "Operation not supported") || contains(msg, "Operation not yet implemented")
+        return NotSupportedError()
+    else
+        return UnknownError()
+    end
+end
+
+"""
+    get_object!(buffer, path, conf; decode_content_encoding, decompress, raw_path, if_match, version, progress) -> Int
+
+Send a get request to the object store.
+
+Fetches the data bytes at `path` and writes them to the given `buffer`.
+
+# Arguments
+- `buffer::AbstractVector{UInt8}`: The buffer to write the object data to.
+  The contents of the buffer will be mutated.
+  The buffer must be at least as large as the data.
+  The buffer will not be resized.
+- `path::String`: The location of the data to fetch.
+- `conf::AbstractConfig`: The configuration to use for the request.
+  It includes credentials and other client options.
+
+# Keyword Arguments
+- `decode_content_encoding::Bool`: (Optional) If the object metadata has a `Content-Encoding` of gzip,
+  decompress the data before writing it to the buffer. The buffer must then be large enough for
+  the decompressed data. Objects without a supported `Content-Encoding` are returned as stored.
+  Cannot be combined with `decompress`. Defaults to `false`.
+- `decompress::String`: (Optional) Decompress the data with the given algorithm before writing it to the
+  buffer, regardless of the object metadata, one of `"gzip"`, `"deflate"`, `"zlib"` or `"zstd"`, like
+  `get_object_stream`. This suits objects stored compressed without a `Content-Encoding`, e.g. `.gz` files.
+  The buffer must then be large enough for the decompressed data. Defaults to `""`, no decompression.
+- `raw_path::Bool`: (Optional) Use `path` as the literal key of the object, instead of applying
+  the object_store path encoding rules, which percent-encode characters such as `%` or `+`.
+  This allows reading objects whose keys were written by other clients. Paths that are not
+  valid object_store paths (e.g. with empty segments) are rejected. Defaults to `false`.
+- `if_match::Option{String}`: (Optional) Only read the object if its ETag still matches this one,
+  e.g. to detect concurrent modifications during reads of many objects that must be consistent.
+- `version::Option{String}`: (Optional) Read this version of the object instead of the latest one,
+  as reported by `ObjectMeta` `version` (see `list_objects` with `versions=true`). Requires a versioned
+  S3 bucket or an Azure storage account with blob versioning enabled.
+- `progress`: (Optional) A function or callable object called as `progress(nbytes_transferred, nbytes_total)`
+  while the object is downloaded, on a separate task. Updates are throttled to at most one every
+  100ms, and a final update is reported once the download completes.
+
+# Returns
+- `nbytes::Int`: The number of bytes read from the object store and written to the buffer.
+  That is, `buffer[1:nbytes]` will contain the object data.
+
+# Throws
+- `GetException`: If the request fails for any reason, including if the `buffer` is too small,
+  in which case `is_buffer_too_small` is true for the exception and `required_buffer_size` gives
+  the size the buffer needs to have.
+  If the object does not match `if_match`, `is_precondition_failed` is true for the exception.
+  If the `version` does not exist, `status_code` is 404 for the exception.
+"""
+function get_object!(
+    buffer::AbstractVector{UInt8},
+    path::String,
+    conf::AbstractConfig;
+    decode_content_encoding::Bool=false,
+    decompress::String="",
+    raw_path::Bool=false,
+    if_match::Option{String}=nothing,
+    version::Option{String}=nothing,
+    progress=nothing
+)
+    check_decompress(decompress)
+    if decode_content_encoding && !isempty(decompress)
+        throw(ArgumentError("decode_content_encoding and decompress should not be provided together"))
+    end
+    # Empty strings tell the native library the get is unconditional and of the latest version
+    etag = something(if_match, "")
+    version_id = something(version, "")
+    response = Response()
+    size = length(buffer)
+    ct = current_task()
+    event = Base.Event()
+    handle = pointer_from_objref(event)
+    config = into_config(conf)
+    with_progress(progress) do progress_handle
+        while true
+            preserve_task(ct)
+            result = GC.@preserve buffer config response event try
+                result = @ccall rust_lib.get(
+                    path::Cstring,
+                    buffer::Ref{Cuchar},
+                    size::Culonglong,
+                    decode_content_encoding::Cuchar,
+                    decompress::Cstring,
+                    raw_path::Cuchar,
+                    etag::Cstring,
+                    version_id::Cstring,
+                    config::Ref{Config},
+                    response::Ref{Response},
+                    handle::Ptr{Cvoid},
+                    progress_handle::Ptr{Cvoid}
+                )::Cint
+
+                wait_or_cancel(event, response)
+
+                result
+            finally
+                unpreserve_task(ct)
+            end
+
+            if result == 2
+                # backoff
+                sleep(0.01)
+                continue
+            end
+
+            @throw_on_error(response, "get", GetException)
+
+            return Int(response.length)
+        end
+    end
+end
+
+mutable struct AllocResponseFFI
+    result::Cint
+    length::Culonglong
+    buffer::Ptr{UInt8}
+    http_status::Cushort
+    error_message::Ptr{Cchar}
+    context::Ptr{Cvoid}
+
+    AllocResponseFFI() = new(-1, 0, C_NULL, 0, C_NULL, C_NULL)
+end
+
+"""
+    get_object(path, conf) -> Vector{UInt8}
+
+Send a get request to the object store.
+
+Fetches the data bytes at `path` into a newly allocated buffer sized to the object.
+Unlike `get_object!`, the size of the object does not need to be known beforehand,
+avoiding a `head_object` request before the get.
+
+# Arguments
+- `path::String`: The location of the data to fetch.
+- `conf::AbstractConfig`: The configuration to use for the request.
+  It includes credentials and other client options.
+
+# Returns
+- `data::Vector{UInt8}`: The object data.
+
+# Throws
+- `GetException`: If the request fails for any reason.
+"""
+function get_object(path::String, conf::AbstractConfig)
+    response = AllocResponseFFI()
+    ct = current_task()
+    event = Base.Event()
+    handle = pointer_from_objref(event)
+    config = into_config(conf)
+    while true
+        preserve_task(ct)
+        result = GC.@preserve config response event try
+            result = @ccall rust_lib.get_alloc(
+                path::Cstring,
+                config::Ref{Config},
+                response::Ref{AllocResponseFFI},
+                handle::Ptr{Cvoid}
+            )::Cint
+
+            wait_or_cancel(event, response)
+
+            result
+        finally
+            unpreserve_task(ct)
+        end
+
+        if result == 2
+            # backoff
+            sleep(0.01)
+            continue
+        end
+
+        @throw_on_error(response, "get", GetException)
+
+        # Copy the data so that its lifetime is managed by Julia, then free the native buffer
+        data = Vector{UInt8}(undef, response.length)
+        if response.buffer != C_NULL
+            unsafe_copyto!(pointer(data), response.buffer, response.length)
+            @ccall rust_lib.destroy_buffer(response.buffer::Ptr{UInt8}, response.length::Culonglong)::Cint
+        end
+        return data
+    end
+end
+
+"""
+    get_object_to_file(path, file_path, conf) -> Int
+
+Send a get request to the object store, writing the data to a local file.
+
+Streams the data bytes at `path` to `file_path` without holding the whole object in memory,
+which suits downloading large objects to local scratch space.
+
+# Arguments
+- `path::String`: The location of the data to fetch.
+- `file_path::String`: The local file to write the data to.
+  The file is created if it does not exist, and truncated otherwise.
+- `conf::AbstractConfig`: The configuration to use for the request.
+  It includes credentials and other client options.
+
+# Returns
+- `nbytes::Int`: The number of bytes written to the file.
+
+# Throws
+- `GetException`: If the request fails for any reason, including if the file cannot be written.
+"""
+function get_object_to_file(path::String, file_path::String, conf::AbstractConfig)
+    response = Response()
+    ct = current_task()
+    event = Base.Event()
+    handle = pointer_from_objref(event)
+    config = into_config(conf)
+    while true
+        preserve_task(ct)
+        result = GC.@preserve config response event try
+            result = @ccall rust_lib.get_to_file(
+                path::Cstring,
+                file_path::Cstring,
+                config::Ref{Config},
+                response::Ref{Response},
+                handle::Ptr{Cvoid}
+            )::Cint
+
+            wait_or_cancel(event, response)
+
+            result
+        finally
+            unpreserve_task(ct)
+        end
+
+        if result == 2
+            # backoff
+            sleep(0.01)
+            continue
+        end
+
+        @throw_on_error(response, "get_to_file", GetException)
+
+        return Int(response.length)
+    end
+end
+
+# The algorithms supported by the native library, the empty string disables decompression
+const DECOMPRESS_ALGORITHMS = ("", "gzip", "deflate", "zlib", "zstd")
+
+function check_decompress(decompress::String)
+    decompress in DECOMPRESS_ALGORITHMS && return nothing
+    throw(ArgumentError(
+        "Unsupported decompression algorithm: $(repr(decompress)), should be one of \"gzip\", \"deflate\", \"zlib\" or \"zstd\""
+    ))
+end
+
+"""
+    get_object_from_url!(buffer, url, params) -> Int
+
+Send a get request to the object store for a fully-qualified object URL.
+
+The backend, account and container are derived from `url`, so no preconfigured `AbstractConfig`
+is needed. Clients are cached per derived container, in the same way as for `Config`.
+
+# Arguments
+- `buffer::AbstractVector{UInt8}`: The buffer to write the object data to.
+  The contents of the buffer will be mutated.
+  The buffer must be at least as large as the data.
+  The buffer will not be resized.
+- `url::String`: Url of the object, including the cloud specific url scheme
+  (e.g. `s3://bucket/path/to/object` or `https://account.blob.core.windows.net/container/path/to/object`).
+- `params::Dict{String, String}`: (Optional) A set of key-value pairs to configure access to the object store,
+  as for `Config`.
+
+# Returns
+- `nbytes::Int`: The number of bytes read from the object store and written to the buffer.
+
+# Throws
+- `GetException`: If the request fails for any reason, including if the `url` cannot be parsed,
+  or if the `buffer` is too small (see `get_object!`).
+"""
+function get_object_from_url!(
+    buffer::AbstractVector{UInt8},
+    url::String,
+    params::Dict{String, String}=Dict{String, String}()
+)
+    response = Response()
+    size = length(buffer)
+    ct = current_task()
+    event = Base.Event()
+    handle = pointer_from_objref(event)
+    # The object path is split from the url by the native library
+    config = Config(url, params)
+    while true
+        preserve_task(ct)
+        result = GC.@preserve buffer config response event try
+            result = @ccall rust_lib.get_url(
+                buffer::Ref{Cuchar},
+                size::Culonglong,
+                config::Ref{Config},
+                response::Ref{Response},
+                handle::Ptr{Cvoid}
+            )::Cint
+
+            wait_or_cancel(event, response)
+
+            result
+        finally
+            unpreserve_task(ct)
+        end
+
+        if result == 2
+            # backoff
+            sleep(0.01)
+            continue
+        end
+
+        @throw_on_error(response, "get_url", GetException)
+
+        return Int(response.length)
+    end
+end
+
+"""
+    get_object_range!(buffer, path, conf; offset, suffix_length) -> Int
+
+Send a ranged get request to the object store.
+
+Fetches a byte range of the object at `path` and writes it to the given `buffer`.
+By default up to `length(buffer)` bytes starting at `offset` are fetched.
+If `suffix_length` is given the last `suffix_length` bytes of the object are fetched instead,
+which avoids a separate request for the object size when reading trailing metadata
+(e.g. Parquet footers).
+
+# Arguments
+- `buffer::AbstractVector{UInt8}`: The buffer to write the object data to.
+  The contents of the buffer will be mutated.
+  The buffer will not be resized.
+- `path::String`: The location of the data to fetch.
+- `conf::AbstractConfig`: The configuration to use for the request.
+  It includes credentials and other client options.
+
+# Keyword Arguments
+- `offset::Int`: (Optional) Zero-based position of the first byte to fetch. Defaults to `0`.
+- `suffix_length::Option{Int}`: (Optional) Number of bytes to fetch from the end of the object
+  (conflicts with `offset`). Must not be greater than `length(buffer)`.
+
+# Returns
+- `nbytes::Int`: The number of bytes read from the object store and written to the buffer.
+  This is smaller than the requested range if the object ends before the range does.
+
+# Throws
+- `GetException`: If the request fails for any reason.
+"""
+function get_object_range!(
+    buffer::AbstractVector{UInt8},
+    path::String,
+    conf::AbstractConfig;
+    offset::Int=0,
+    suffix_length::Option{Int}=nothing
+)
+    size = length(buffer)
+    if !isnothing(suffix_length)
+        offset == 0 || throw(ArgumentError("Should provide either an offset or a suffix_length"))
+        suffix_length > 0 || throw(ArgumentError("suffix_length must be positive"))
+        suffix_length <= size || throw(ArgumentError("suffix_length must not be greater than the buffer length"))
+        # A negative start tells the native library to fetch a suffix range of `size` bytes
+        size = suffix_length
+        start = -suffix_length
+    else
+        offset >= 0 || throw(ArgumentError("offset must not be negative"))
+        start = offset
+    end
+    response = Response()
+    ct = current_task()
+    event = Base.Event()
+    handle = pointer_from_objref(event)
+    config = into_config(conf)
+    while true
+        preserve_task(ct)
+        result = GC.@preserve buffer config response event try
+            result = @ccall rust_lib.get_range(
+                path::Cstring,
+                buffer::Ref{Cuchar},
+                size::Culonglong,
+                start::Clonglong,
+                config::Ref{Config},
+                response::Ref{Response},
+                handle::Ptr{Cvoid}
+            )::Cint
+
+            wait_or_cancel(event, response)
+
+            result
+        finally
+            unpreserve_task(ct)
+        end
+
+        if result == 2
+            # backoff
+            sleep(0.01)
+            continue
+        end
+
+        @throw_on_error(response, "get_range", GetException)
+
+        return Int(response.length)
+    end
+end
+
+"""
+    get_object_ranges!(buffers, path, conf; offsets) -> Vector{Int}
+
+Send a get request for many byte ranges of one object to the object store.
+
+Fetches the range of `length(buffers[i])` bytes starting at `offsets[i]` of the object at `path`
+into `buffers[i]`, for each `i`. Nearby ranges are coalesced into fewer requests by the native
+library, which is far more efficient than separate `get_object_range!` calls when reading many
+scattered ranges, e.g. the column chunks of columnar formats.
+
+# Arguments
+- `buffers::AbstractVector{Vector{UInt8}}`: The buffers to write the ranges to, whose lengths
+  are the lengths of the ranges. The contents of the buffers will be mutated.
+  The buffers will not be resized.
+- `path::String`: The location of the data to fetch.
+- `conf::AbstractConfig`: The configuration to use for the request.
+  It includes credentials and other client options.
+
+# Keyword Arguments
+- `offsets::AbstractVector{Int}`: Zero-based position of the first byte of each range,
+  one per buffer.
+
+# Returns
+- `nbytes::Vector{Int}`: The number of bytes written to each buffer, which is always its length.
+
+# Throws
+- `GetException`: If the request fails for any reason, including if a range extends past the end
+  of the object.
+"""
+function get_object_ranges!(
+    buffers::AbstractVector{Vector{UInt8}},
+    path::String,
+    conf::AbstractConfig;
+    offsets::AbstractVector{Int}
+)
+    length(buffers) == length(offsets) || throw(ArgumentError("Should provide one offset per buffer"))
+    all(>=(0), offsets) || throw(ArgumentError("Offsets must not be negative"))
+    count = length(buffers)
+    starts = Culonglong[offset for offset in offsets]
+    sizes = Culonglong[length(buffer) for buffer in buffers]
+    # The native library reads this array until the request completes, so it is preserved with the buffers
+    buffer_ptrs = Ptr{Cuchar}[pointer(buffer) for buffer in buffers]
+    response = Response()
+    ct = current_task()
+    event = Base.Event()
+    handle = pointer_from_objref(event)
+    config = into_config(conf)
+    while true
+        preserve_task(ct)
+        result = GC.@preserve buffers buffer_ptrs starts sizes config response event try
+            result = @ccall rust_lib.get_ranges(
+                path::Cstring,
+                starts::Ptr{Culonglong},
+                buffer_ptrs::Ptr{Ptr{Cuchar}},
+                sizes::Ptr{Culonglong},
+                count::Culonglong,
+                config::Ref{Config},
+                response::Ref{Response},
+                handle::Ptr{Cvoid}
+            )::Cint
+
+            wait_or_cancel(event, response)
+
+            result
+        finally
+            unpreserve_task(ct)
+        end
+
+        if result == 2
+            # backoff
+            sleep(0.01)
+            continue
+        end
+
+        @throw_on_error(response, "get_ranges", GetException)
+
+        return Int[length(buffer) for buffer in buffers]
+    end
+end
+
+struct BatchEntryFFI
+    result::Cint
+    length::Culonglong
+    http_status::Cushort
+    error_message::Ptr{Cchar}
+
+    BatchEntryFFI() = new(-1, 0, 0, C_NULL)
+end
+
+"""
+    get_objects!(buffers, paths, conf) -> Vector{Int}
+
+Send a batch of get requests to the object store.
+
+Fetches the data bytes at each of the `paths` and writes them to the corresponding buffer in `buffers`.
+The requests are dispatched together and processed concurrently, and the calling task is notified
+once after all of them complete. This amortizes the per-request overhead when fetching many small objects.
+
+# Arguments
+- `buffers::AbstractVector{Vector{UInt8}}`: The buffers to write the object data to, one per path.
+  The contents of the buffers will be mutated.
+  Each buffer must be at least as large as the corresponding data.
+  The buffers will not be resized.
+- `paths::AbstractVector{String}`: The locations of the data to fetch.
+- `conf::AbstractConfig`: The configuration to use for the requests.
+  It includes credentials and other client options.
+
+# Returns
+- `nbytes::Vector{Int}`: The number of bytes read from the object store for each path.
+
+# Throws
+- `GetException`: If any of the requests fails for any reason.
+  The message identifies the path of the first failed request.
+  If its buffer is too small, `is_buffer_too_small` is true for the exception and `required_buffer_size`
+  gives the size the buffer needs to have.
+"""
+function get_objects!(
+    buffers::AbstractVector{Vector{UInt8}},
+    paths::AbstractVector{String},
+    conf::AbstractConfig
+)
+    length(buffers) == length(paths) || throw(ArgumentError("Should provide one buffer per path"))
+    count = length(paths)
+    sizes = Culonglong[length(buffer) for buffer in buffers]
+    entries = [BatchEntryFFI() for _ in 1:count]
+    # The native library reads these arrays until the batch completes, so they are preserved
+    # with the paths and buffers they point to
+    path_ptrs = Cstring[Base.unsafe_convert(Cstring, path) for path in paths]
+    buffer_ptrs = Ptr{Cuchar}[pointer(buffer) for buffer in buffers]
+    response = Response()
+    ct = current_task()
+    event = Base.Event()
+    handle = pointer_from_objref(event)
+    config = into_config(conf)
+    while true
+        preserve_task(ct)
+        result = GC.@preserve buffers paths path_ptrs buffer_ptrs sizes entries config response event try
+            result = @ccall rust_lib.get_batch(
+                path_ptrs::Ptr{Cstring},
+                buffer_ptrs::Ptr{Ptr{Cuchar}},
+                sizes::Ptr{Culonglong},
+                count::Culonglong,
+                entries::Ptr{BatchEntryFFI},
+                config::Ref{Config},
+                response::Ref{Response},
+                handle::Ptr{Cvoid}
+            )::Cint
+
+            wait_or_cancel(event, response)
+
+            result
+        finally
+            unpreserve_task(ct)
+        end
+
+        if result == 2
+            # backoff
+            sleep(0.01)
+            continue
+        end
+
+        @throw_on_error(response, "get_batch", GetException)
+
+        # Collect all error messages so they are all reclaimed before throwing
+        errors = [take_cstring(entry.error_message) for entry in entries]
+        for (path, entry, err) in zip(paths, entries, errors)
+            msg = string("failed to process get of ", repr(path), " with error: ", something(err, "unknown error"))
+            if entry.result == 5
+                # The length of the entry is the size the buffer needs to have
+                throw(GetException(msg, BufferTooSmallError(Int(entry.length))))
+            elseif entry.result != 0
+                throw(GetException(msg, Int(entry.http_status)))
+            end
+        end
+
+        return Int[entry.length for entry in entries]
+    end
+end
+
+"""
+    put_object(buffer, path, conf; mode, expected_etag, expected_version, content_md5, verify,
+        cache_control, content_disposition, storage_class, ignore_unsupported_storage_class, progress) -> Int
+
+Send a put request to the object store.
+
+Atomically writes the data bytes in `buffer` to `path`.
+
+# Arguments
+- `buffer::AbstractVector{UInt8}`: The data to write to the object store.
+  This buffer will not be mutated.
+  The native library reads the buffer in place, without copying it, and never accesses it after
+  `put_object` returns, even if the calling task is interrupted. So memory-mapped data can be
+  written directly, provided it is only unmapped once the call has returned.
+  An empty buffer writes an empty object, e.g. to reset a marker object.
+- `path::String`: The location to write data to.
+- `conf::AbstractConfig`: The configuration to use for the request.
+  It includes credentials and other client options.
+
+# Keyword Arguments
+- `mode::Symbol`: (Optional) The write semantics, one of:
+  - `:overwrite`: unconditionally write the object (default).
+  - `:create`: only write the object if it does not exist yet.
+  - `:update`: only write the object if it still matches `expected_etag` and/or `expected_version`,
+    which allows compare-and-swap updates.
+- `expected_etag::Option{String}`: (Optional) The ETag the object must have for `mode=:update`.
+- `expected_version::Option{String}`: (Optional) The version the object must have for `mode=:update`.
+- `content_md5::Bool`: (Optional) Compute the MD5 digest of the data and send it with the upload
+  (of each part, for multipart uploads), so that the server rejects data corrupted in transit.
+  This costs CPU time proportional to the size of the data. Defaults to `false`.
+- `verify::Bool`: (Optional) After the write succeeds, send a head request to confirm that the object
+  is readable with the expected size before returning. This guards against eventually consistent
+  backends where a read right after the write could fail. Defaults to `false`.
+- `cache_control::Option{String}`: (Optional) The `Cache-Control` header served with the object.
+- `content_disposition::Option{String}`: (Optional) The `Content-Disposition` header served with the object.
+- `storage_class::Option{String}`: (Optional) The storage class (S3, e.g. `"STANDARD_IA"` or `"GLACIER"`)
+  or access tier (Azure, e.g. `"Cool"` or `"Archive"`) to write the object to, avoiding a separate
+  request to change it after the upload. The value is passed to the backend as given.
+- `ignore_unsupported_storage_class::Bool`: (Optional) Write the object with the default storage class
+  if the backend does not support `storage_class` (e.g. a class the server does not know),
+  instead of failing the request. Defaults to `false`.
+- `progress`: (Optional) A function or callable object called as `progress(nbytes_transferred, nbytes_total)`
+  while the object is uploaded, on a separate task. Updates are throttled to at most one every
+  100ms, and a final update is reported once the upload completes.
+
+# Returns
+- `nbytes::Int`: The number of bytes written to the object store.
+  Is always equal to `length(buffer)`.
+
+# Throws
+- `PutException`: If the request fails for any reason.
+  If the condition of `mode=:update` is not met, `is_precondition_failed` is true for the exception.
+  If the object exists for `mode=:create`, `is_already_exists` is true for the exception.
+  If `verify` is set and the written object cannot be confirmed, the exception describes the mismatch.
+  If `content_md5` is set and the server detects corrupted data, `is_checksum_mismatch` is true for the exception.
+"""
+function put_object(buffer::AbstractVector{UInt8}, path::String, conf::AbstractConfig; kwargs...)
+    return put_object_with_result(buffer, path, conf; kwargs...).nbytes
+end
+
+function put_options_string(;
+    mode::Symbol=:overwrite,
+    expected_etag::Option{String}=nothing,
+    expected_version::Option{String}=nothing,
+    content_md5::Bool=false,
+    verify::Bool=false,
+    cache_control::Option{String}=nothing,
+    content_disposition::Option{String}=nothing,
+    storage_class::Option{String}=nothing,
+    ignore_unsupported_storage_class::Bool=false
+)
+    opts = Dict{String, String}()
+    if mode === :overwrite || mode === :create
+        if !isnothing(expected_etag) || !isnothing(expected_version)
+            throw(ArgumentError("expected_etag and expected_version are only supported with mode=:update"))
+        end
+        mode === :create && (opts["mode"] = "create")
+    elseif mode === :update
+        if isnothing(expected_etag) && isnothing(expected_version)
+            throw(ArgumentError("Should provide an expected_etag or an expected_version with mode=:update"))
+        end
+        opts["mode"] = "update"
+        !isnothing(expected_etag) && (opts["expected_etag"] = expected_etag)
+        !isnothing(expected_version) && (opts["expected_version"] = expected_version)
+    else
+        throw(ArgumentError("Unsupported put mode: $(repr(mode)), should be one of :overwrite, :create or :update"))
+    end
+    content_md5 && (opts["content_md5"] = "true")
+    verify && (opts["verify"] = "true")
+    !isnothing(cache_control) && (opts["cache_control"] = cache_control)
+    !isnothing(content_disposition) && (opts["content_disposition"] = content_disposition)
+    !isnothing(storage_class) && (opts["storage_class"] = storage_class)
+    ignore_unsupported_storage_class && (opts["ignore_unsupported_storage_class"] = "true")
+    # Most requests use the defaults, so avoid serializing an empty object
+    return isempty(opts) ? "" : JSON3.write(opts)
+end
+
+"""
+    put_object_with_result(buffer, path, conf; kwargs...) -> PutResult
+
+Send a put request to the object store, like `put_object`, returning the ETag and version
+that the server assigned to the written object.
+
+On versioned buckets the returned version can be used to pin subsequent reads to the exact
+object that was written.
+
+# Arguments
+- `buffer::AbstractVector{UInt8}`: The data to write to the object store.
+  This buffer will not be mutated.
+- `path::String`: The location to write data to.
+- `conf::AbstractConfig`: The configuration to use for the request.
+  It includes credentials and other client options.
+
+# Keyword Arguments
+Accepts the same keyword arguments as `put_object`.
+
+# Returns
+- `result::PutResult`: The number of bytes written along with the ETag and version of the object,
+  when reported by the backend.
+
+# Throws
+- `PutException`: If the request fails for any reason.
+"""
+function put_object_with_result(
+    buffer::AbstractVector{UInt8},
+    path::String,
+    conf::AbstractConfig;
+    progress=nothing,
+    kwargs...
+)
+    options = put_options_string(; kwargs...)
+    response = PutResponseFFI()
+    size = length(buffer)
+    ct = current_task()
+    event = Base.Event()
+    handle = pointer_from_objref(event)
+    config = into_config(conf)
+    with_progress(progress) do progress_handle
+        while true
+            preserve_task(ct)
+            result = GC.@preserve buffer config response event try
+                result = @ccall rust_lib.put(
+                    path::Cstring,
+                    buffer::Ref{Cuchar},
+                    size::Culonglong,
+                    options::Cstring,
+                    config::Ref{Config},
+                    response::Ref{PutResponseFFI},
+                    handle::Ptr{Cvoid},
+                    progress_handle::Ptr{Cvoid}
+                )::Cint
+
+                wait_or_cancel(event, response)
+
+                result
+            finally
+                unpreserve_task(ct)
+            end
+
+            if result == 2
+                # backoff
+                sleep(0.01)
+                continue
+            end
+
+            @throw_on_error(response, "put", PutException)
+
+            return PutResult(
+                Int(response.length),
+                take_cstring(response.e_tag),
+                take_cstring(response.version)
+            )
+        end
+    end
+end
+
+"""
+    put_object_from_file(file_path, path, conf) -> Int
+
+Send a put request to the object store, reading the data from a local file.
+
+Streams the contents of `file_path` to `path` without loading the whole file in memory.
+Files larger than the multipart put threshold of the `StaticConfig` are uploaded in parts.
+
+# Arguments
+- `file_path::String`: The local file to read the data from.
+- `path::String`: The location to write data to.
+- `conf::AbstractConfig`: The configuration to use for the request.
+  It includes credentials and other client options.
+
+# Returns
+- `nbytes::Int`: The number of bytes written to the object store.
+  Is always equal to the size of the file.
+
+# Throws
+- `PutException`: If the request fails for any reason, including if the file cannot be read.
+"""
+function put_object_from_file(file_path::String, path::String, conf::AbstractConfig)
+    response = Response()
+    ct = current_task()
+    event = Base.Event()
+    handle = pointer_from_objref(event)
+    config = into_config(conf)
+    while true
+        preserve_task(ct)
+        result = GC.@preserve config response event try
+            result = @ccall rust_lib.put_from_file(
+                file_path::Cstring,
+                path::Cstring,
+                config::Ref{Config},
+                response::Ref{Response},
+                handle::Ptr{Cvoid}
+            )::Cint
+
+            wait_or_cancel(event, response)
+
+            result
+        finally
+            unpreserve_task(ct)
+        end
+
+        if result == 2
+            # backoff
+            sleep(0.01)
+            continue
+        end
+
+        @throw_on_error(response, "put_from_file", PutException)
+
+        return Int(response.length)
+    end
+end
+
+"""
+    delete_object(path, conf; expected_etag, ignore_not_found)
+
+Send a delete request to the object store.
+
+# Arguments
+- `path::String`: The location of the object to delete.
+- `conf::AbstractConfig`: The configuration to use for the request.
+  It includes credentials and other client options.
+
+# Keyword Arguments
+- `expected_etag::Option{String}`: (Optional) Only delete the object if its ETag still matches,
+  which prevents deleting an object that was replaced since it was last read.
+  Only supported by backends with conditional deletes (e.g. Azure Blob), others fail the request.
+- `ignore_not_found::Bool`: (Optional) Treat deleting a non-existing object as a success on all backends,
+  so that deletes are idempotent, e.g. for cleanups that can be re-run. Defaults to `false`.
+
+# Throws
+- `DeleteException`: If the request fails for any reason. Note that S3 will treat a delete request
+  to a non-existing object as a success, while Azure Blob will treat it as a 404 error
+  unless `ignore_not_found` is set.
+  If the object does not match `expected_etag`, `is_precondition_failed` is true for the exception.
+"""
+function delete_object(
+    path::String,
+    conf::AbstractConfig;
+    expected_etag::Option{String}=nothing,
+    ignore_not_found::Bool=false
+)
+    # An empty string tells the native library the delete is unconditional
+    etag = something(expected_etag, "")
+    response = Response()
+    ct = current_task()
+    event = Base.Event()
+    handle = pointer_from_objref(event)
+    config = into_config(conf)
+    while true
+        preserve_task(ct)
+        result = GC.@preserve config response event try
+            result = @ccall rust_lib.delete(
+                path::Cstring,
+                etag::Cstring,
+                ignore_not_found::Cuchar,
+                config::Ref{Config},
+                response::Ref{Response},
+                handle::Ptr{Cvoid}
+            )::Cint
+
+            wait_or_cancel(event, response)
+
+            result
+        finally
+            unpreserve_task(ct)
+        end
+
+        if result == 2
+            # backoff
+            sleep(0.01)
+            continue
+        end
+
+        @throw_on_error(response, "delete_object", DeleteException)
+
+        return nothing
+    end
+end
+
+"""
+    copy_object(from, to, conf; overwrite)
+
+Send a copy request to the object store.
+
+Copies the object at `from` to `to` within the same bucket or container, without transferring
+the data through the client.
+
+# Arguments
+- `from::String`: The location of the object to copy.
+- `to::String`: The location to copy the object to.
+- `conf::AbstractConfig`: The configuration to use for the request.
+  It includes credentials and other client options.
+
+# Keyword Arguments
+- `overwrite::Bool`: (Optional) Whether to replace an existing object at `to`. With `false`, the copy
+  atomically fails if `to` exists, so it can be used to claim a location among concurrent writers.
+  S3 only supports this when `AWSConfig` `copy_if_not_exists` is configured. Defaults to `true`.
+
+# Throws
+- `CopyException`: If the request fails for any reason.
+  If `to` exists with `overwrite=false`, `is_already_exists` is true for the exception.
+  If the backend cannot copy atomically, `is_not_supported` is true for the exception.
+"""
+function copy_object(from::String, to::String, conf::AbstractConfig; overwrite::Bool=true)
+    response = Response()
+    ct = current_task()
+    event = Base.Event()
+    handle = pointer_from_objref(event)
+    config = into_config(conf)
+    while true
+        preserve_task(ct)
+        result = GC.@preserve config response event try
+            result = @ccall rust_lib.copy(
+                from::Cstring,
+                to::Cstring,
+                overwrite::Cuchar,
+                config::Ref{Config},
+                response::Ref{Response},
+                handle::Ptr{Cvoid}
+            )::Cint
+
+            wait_or_cancel(event, response)
+
+            result
+        finally
+            unpreserve_task(ct)
+        end
+
+        if result == 2
+            # backoff
+            sleep(0.01)
+            continue
+        end
+
+        @throw_on_error(response, "copy", CopyException)
+
+        return nothing
+    end
+end
+
+"""
+    copy_object(from, from_conf, to, to_conf) -> Int
+
+Copy an object between two configurations, e.g. across storage accounts or between backends.
+
+Backends cannot copy across accounts server-side, so the object at `from` is streamed through the
+native library from `from_conf` to `to` on `to_conf`, using multipart operations if it is large.
+The object data is not transferred to Julia.
+
+# Arguments
+- `from::String`: The location of the object to copy.
+- `from_conf::AbstractConfig`: The configuration to read the object with.
+- `to::String`: The location to copy the object to, always replacing an existing object.
+- `to_conf::AbstractConfig`: The configuration to write the object with.
+
+# Returns
+- `nbytes::Int`: The number of bytes copied.
+
+# Throws
+- `CopyException`: If the request fails for any reason.
+"""
+function copy_object(from::String, from_conf::AbstractConfig, to::String, to_conf::AbstractConfig)
+    response = Response()
+    ct = current_task()
+    event = Base.Event()
+    handle = pointer_from_objref(event)
+    src_config = into_config(from_conf)
+    dst_config = into_config(to_conf)
+    while true
+        preserve_task(ct)
+        result = GC.@preserve src_config dst_config response event try
+            result = @ccall rust_lib.cross_copy(
+                from::Cstring,
+                src_config::Ref{Config},
+                to::Cstring,
+                dst_config::Ref{Config},
+                response::Ref{Response},
+                handle::Ptr{Cvoid}
+            )::Cint
+
+            wait_or_cancel(event, response)
+
+            result
+        finally
+            unpreserve_task(ct)
+        end
+
+        if result == 2
+            # backoff
+            sleep(0.01)
+            continue
+        end
+
+        @throw_on_error(response, "cross_copy", CopyException)
+
+        return Int(response.length)
+    end
+end
+
+mutable struct SignedUrlResponseFFI
+    result::Cint
+    url::Ptr{Cchar}
+    http_status::Cushort
+    error_message::Ptr{Cchar}
+    context::Ptr{Cvoid}
+
+    SignedUrlResponseFFI() = new(-1, C_NULL, 0, C_NULL, C_NULL)
+end
+
+"""
+    signed_url(path, conf; method, expires_in_secs) -> String
+
+Generate a presigned URL for the object at `path`.
+
+The URL grants time-limited access to the object without any other credentials, so it can be
+handed out to clients that cannot use this library. For S3 the request is presigned, for Azure
+a SAS token is generated.
+
+# Arguments
+- `path::String`: The location of the object.
+- `conf::AbstractConfig`: The configuration to use for signing, which must include credentials,
+  e.g. an `AzureConfig` with a `storage_account_key`.
+
+# Keyword Arguments
+- `method::Symbol`: (Optional) The HTTP method the URL is valid for, `:GET` or `:PUT`. Defaults to `:GET`.
+- `expires_in_secs::Int`: (Optional) The time in seconds for which the URL is valid. Defaults to one hour.
+
+# Returns
+- `url::String`: The presigned URL.
+
+# Throws
+- `SignException`: If the URL cannot be generated, e.g. if the backend or credentials do not support
+  signing, in which case `is_not_supported` is true for the exception.
+"""
+function signed_url(path::String, conf::AbstractConfig; method::Symbol=:GET, expires_in_secs::Int=3600)
+    if !(method === :GET || method === :PUT)
+        throw(ArgumentError("Unsupported method: $(repr(method)), should be one of :GET or :PUT"))
+    end
+    expires_in_secs > 0 || throw(ArgumentError("expires_in_secs must be positive, got $(expires_in_secs)"))
+    method_str = string(method)
+    response = SignedUrlResponseFFI()
+    ct = current_task()
+    event = Base.Event()
+    handle = pointer_from_objref(event)
+    config = into_config(conf)
+    while true
+        preserve_task(ct)
+        result = GC.@preserve config response event try
+            result = @ccall rust_lib.signed_url(
+                path::Cstring,
+                method_str::Cstring,
+                expires_in_secs::Culonglong,
+                config::Ref{Config},
+                response::Ref{SignedUrlResponseFFI},
+                handle::Ptr{Cvoid}
+            )::Cint
+
+            wait_or_cancel(event, response)
+
+            result
+        finally
+            unpreserve_task(ct)
+        end
+
+        if result == 2
+            # backoff
+            sleep(0.01)
+            continue
+        end
+
+        @throw_on_error(response, "signed_url", SignException)
+
+        return take_cstring(response.url)::String
+    end
+end
+
+"""
+    connection_info(conf) -> Dict{String, String}
+
+Describe how the native library resolves the connection of `conf`, for diagnostics, e.g. to tell
+configuration mistakes apart from backend failures. Credentials are never included.
+
+# Returns
+- `info::Dict{String, String}`: Includes the `"backend"` type (e.g. `"azure"` or `"s3"`) and the
+  `"endpoint"` requests are sent to, as well as the `"region"` for S3.
+
+# Throws
+- `ArgumentError`: If the configuration is invalid.
+"""
+function connection_info(conf::AbstractConfig)
+    config = into_config(conf)
+    ptr = @ccall rust_lib.connection_info(config::Ref{Config})::Ptr{Cchar}
+    ptr == C_NULL && throw(ArgumentError("Invalid configuration: $(repr(conf))"))
+    return JSON3.read(take_cstring(ptr), Dict{String, String})
+end
+
+"""
+    cancel_requests(conf) -> Int
+
+Cancel all in-flight requests using the connection described by `conf`, e.g. when tearing down a tenant.
+
+Each cancelled request fails with an exception for which `is_cancelled` is true, without waiting
+for its transfer to complete. Requests sent after this call are not affected.
+
+# Returns
+- `count::Int`: The number of requests that were cancelled.
+"""
+function cancel_requests(conf::AbstractConfig)
+    config = into_config(conf)
+    count = @ccall rust_lib.cancel_connection(config::Ref{Config})::Culonglong
+    return Int(count)
+end
+
+"""
+    check_connection(conf; round_trip)
+
+Verify that the object store described by `conf` is reachable and that its credentials are valid.
+
+A cheap request is sent that does not depend on any particular object existing
+(listing at most one object of the container), so this is suitable for readiness probes.
+
+# Arguments
+- `conf::AbstractConfig`: The configuration to check.
+  It includes credentials and other client options.
+
+# Keyword Arguments
+- `round_trip::Bool`: (Optional) Instead of listing, put a small sentinel object, get it back,
+  verify its contents and delete it. This also validates write and delete permissions end-to-end,
+  e.g. to catch misconfigurations on deployment, at the cost of a few requests. Defaults to `false`.
+
+# Throws
+- `CheckConnectionException`: If the request fails for any reason, e.g. `is_permission_denied`
+  for invalid credentials or `is_connection` for an unreachable host.
+"""
+function check_connection(conf::AbstractConfig; round_trip::Bool=false)
+    response = Response()
+    ct = current_task()
+    event = Base.Event()
+    handle = pointer_from_objref(event)
+    config = into_config(conf)
+    while true
+        preserve_task(ct)
+        result = GC.@preserve config response event try
+            result = @ccall rust_lib.ping(
+                round_trip::Cuchar,
+                config::Ref{Config},
+                response::Ref{Response},
+                handle::Ptr{Cvoid}
+            )::Cint
+
+            wait_or_cancel(event, response)
+
+            result
+        finally
+            unpreserve_task(ct)
+        end
+
+        if result == 2
+            # backoff
+            sleep(0.01)
+            continue
+        end
+
+        @throw_on_error(response, "ping", CheckConnectionException)
+
+        return nothing
+    end
+end
+
+function warmup_connection(conf::AbstractConfig)
+    response = Response()
+    ct = current_task()
+    event = Base.Event()
+    handle = pointer_from_objref(event)
+    config = into_config(conf)
+    while true
+        preserve_task(ct)
+        result = GC.@preserve config response event try
+            result = @ccall rust_lib.warmup(
+                config::Ref{Config},
+                response::Ref{Response},
+                handle::Ptr{Cvoid}
+            )::Cint
+
+            wait_or_cancel(event, response)
+
+            result
+        finally
+            unpreserve_task(ct)
+        end
+
+        if result == 2
+            # backoff
+            sleep(0.01)
+            continue
+        end
+
+        @throw_on_error(response, "warmup", CheckConnectionException)
+
+        return nothing
+    end
+end
+
+"""
+    warmup_connections(confs) -> Vector{Bool}
+
+Build the clients of the given configurations and insert them into the client cache of the native
+library, so that the first request of each does not pay the cost of building its client
+(e.g. fetching credentials). No request is sent to the object stores.
+
+The clients are built concurrently, the effect can be observed with the `client_builds` of `current_metrics`.
+Failures are logged and the client is built again on the first request, as usual. A failure to build a
+client is reported as a `CheckConnectionException`, like a failed `check_connection`, so the same
+predicates apply to it (e.g. `is_permission_denied`).
+
+# Arguments
+- `confs::AbstractVector{<:AbstractConfig}`: The configurations to warm up.
+
+# Returns
+- `success::Vector{Bool}`: Whether the client of each configuration was built, in the order of `confs`.
+"""
+function warmup_connections(confs::AbstractVector{<:AbstractConfig})
+    return asyncmap(confs) do conf
+        try
+            warmup_connection(conf)
+            return true
+        catch e
+            e isa CheckConnectionException || rethrow()
+            @warn "Failed to warm up connection" conf exception=e
+            return false
+        end
+    end
+end
+
+struct ObjectMetaFFI
+    location::Ptr{Cchar}
+    last_modified::Culonglong
+    size::Culonglong
+    e_tag::Ptr{Cchar}
+    version::Ptr{Cchar}
+    is_latest::Cuchar
+    # Zero if the backend did not report the last modified time
+    has_last_modified::Cuchar
+end
+
+struct ListEntriesFFI
+    ptr::Ptr{ObjectMetaFFI}
+    len::Culonglong
+end
+
+struct KeyValueFFI
+    key::Ptr{Cchar}
+    value::Ptr{Cchar}
+end
+
+struct KeyValuesFFI
+    ptr::Ptr{KeyValueFFI}
+    len::Culonglong
+end
+
+mutable struct ListResponseFFI
+    result::Cint
+    entries::ListEntriesFFI
+    http_status::Cushort
+    error_message::Ptr{Cchar}
+    context::Ptr{Cvoid}
+
+    ListResponseFFI() = new(-1, ListEntriesFFI(C_NULL, 0), 0, C_NULL, C_NULL)
+end
+
+mutable struct PrefixSizeResponseFFI
+    result::Cint
+    length::Culonglong
+    count::Culonglong
+    http_status::Cushort
+    error_message::Ptr{Cchar}
+    context::Ptr{Cvoid}
+
+    PrefixSizeResponseFFI() = new(-1, 0, 0, 0, C_NULL, C_NULL)
+end
+
+struct MultipartUploadFFI
+    location::Ptr{Cchar}
+    upload_id::Ptr{Cchar}
+    initiated::Culonglong
+end
+
+struct MultipartUploadsFFI
+    ptr::Ptr{MultipartUploadFFI}
+    len::Culonglong
+end
+
+mutable struct ListUploadsResponseFFI
+    result::Cint
+    uploads::MultipartUploadsFFI
+    http_status::Cushort
+    error_message::Ptr{Cchar}
+    context::Ptr{Cvoid}
+
+    ListUploadsResponseFFI() = new(-1, MultipartUploadsFFI(C_NULL, 0), 0, C_NULL, C_NULL)
+end
+
+mutable struct AbortUploadsResponseFFI
+    result::Cint
+    count::Culonglong
+    http_status::Cushort
+    error_message::Ptr{Cchar}
+    context::Ptr{Cvoid}
+
+    AbortUploadsResponseFFI() = new(-1, 0, 0, C_NULL, C_NULL)
+end
+
+mutable struct HeadResponseFFI
+    result::Cint
+    meta::ObjectMetaFFI
+    metadata::KeyValuesFFI
+    attributes::KeyValuesFFI
+    http_status::Cushort
+    error_message::Ptr{Cchar}
+    context::Ptr{Cvoid}
+
+    HeadResponseFFI() = new(
+        -1,
+        ObjectMetaFFI(C_NULL, 0, 0, C_NULL, C_NULL, 0, 0),
+        KeyValuesFFI(C_NULL, 0),
+        KeyValuesFFI(C_NULL, 0),
+        0,
+        C_NULL,
+        C_NULL
+    )
+end
+
+"""
+    $TYPEDEF
+
+Metadata of an object returned by `list_objects` or `head_object`.
+
+# Fields
+$TYPEDFIELDS
+"""
+struct ObjectMeta
+    "The full path to the object"
+    location::String
+    "The last modified time in milliseconds since the Unix epoch, only meaningful if `has_last_modified`"
+    last_modified::Int64
+    "The size in bytes of the object"
+    size::Int
+    "The unique identifier for the object, if reported by the backend"
+    e_tag::Option{String}
+    "A version identifier for the object, if the backend is versioned"
+    version::Option{String}
+    "Whether this is the current version of the object"
+    is_latest::Bool
+    """
+    The user-defined metadata of the object (e.g. `x-ms-meta-*` or `x-amz-meta-*` headers, without the prefix),
+    only populated by `head_object`
+    """
+    metadata::Option{Dict{String, String}}
+    """
+    The standard HTTP attributes of the object (e.g. `Cache-Control` or `Content-Disposition`,
+    keyed by header name), only populated by `head_object`
+    """
+    attributes::Option{Dict{String, String}}
+    "Whether the backend reported the last modified time of the object"
+    has_last_modified::Bool
+end
+
+# Fields are only ever appended, these keep constructing from the earlier fields working
+ObjectMeta(location, last_modified, size, e_tag, version, is_latest) =
+    ObjectMeta(location, last_modified, size, e_tag, version, is_latest, nothing, nothing, true)
+ObjectMeta(location, last_modified, size, e_tag, version, is_latest, metadata) =
+    ObjectMeta(location, last_modified, size, e_tag, version, is_latest, metadata, nothing, true)
+ObjectMeta(location, last_modified, size, e_tag, version, is_latest, metadata, attributes) =
+    ObjectMeta(location, last_modified, size, e_tag, version, is_latest, metadata, attributes, true)
+
+function convert_object_meta(
+    entry::ObjectMetaFFI,
+    metadata::Option{Dict{String, String}}=nothing,
+    attributes::Option{Dict{String, String}}=nothing
+)
+    return ObjectMeta(
+        unsafe_string(entry.location),
+        Int64(entry.last_modified),
+        Int(entry.size),
+        entry.e_tag == C_NULL ? nothing : unsafe_string(entry.e_tag),
+        entry.version == C_NULL ? nothing : unsafe_string(entry.version),
+        entry.is_latest > 0,
+        metadata,
+        attributes,
+        entry.has_last_modified > 0
+    )
+end
+
+"""
+    last_modified_datetime(meta::ObjectMeta) -> Option{DateTime}
+
+The last modified time of the object as a UTC `DateTime`, or `nothing` if it was not reported by the backend.
+"""
+function last_modified_datetime(meta::ObjectMeta)
+    meta.has_last_modified || return nothing
+    return DateTime(1970) + Millisecond(meta.last_modified)
+end
+
+function convert_list_entries(entries::ListEntriesFFI)
+    result = Vector{ObjectMeta}(undef, entries.len)
+    for i in 1:entries.len
+        result[i] = convert_object_meta(unsafe_load(entries.ptr, i))
+    end
+    return result
+end
+
+"""
+    $TYPEDEF
+
+An in-progress multipart upload returned by `list_multipart_uploads`.
+
+# Fields
+$TYPEDFIELDS
+"""
+struct MultipartUpload
+    "The full path to the object being uploaded"
+    location::String
+    "The identifier of the upload assigned by the backend"
+    upload_id::String
+    "The time the upload was initiated in milliseconds since the Unix epoch"
+    initiated::Int64
+end
+
+function convert_multipart_uploads(uploads::MultipartUploadsFFI)
+    result = Vector{MultipartUpload}(undef, uploads.len)
+    for i in 1:uploads.len
+        upload = unsafe_load(uploads.ptr, i)
+        result[i] = MultipartUpload(
+            unsafe_string(upload.location),
+            unsafe_string(upload.upload_id),
+            Int64(upload.initiated)
+        )
+    end
+    return result
+end
+
+function convert_key_values(kvs::KeyValuesFFI)
+    result = Dict{String, String}()
+    for i in 1:kvs.len
+        kv = unsafe_load(kvs.ptr, i)
+        result[unsafe_string(kv.key)] = unsafe_string(kv.value)
+    end
+    return result
+end
+
+"""
+    list_objects(prefix, conf; versions, max_keys, start_after, sorted, modified_since) -> Vector{ObjectMeta}
+
+Send a list request to the object store.
+
+Lists all objects with the given `prefix`.
+
+# Arguments
+- `prefix::String`: Only objects with this prefix will be returned.
+- `conf::AbstractConfig`: The configuration to use for the request.
+  It includes credentials and other client options.
+
+# Keyword Arguments
+- `versions::Bool`: (Optional) Return every version of each object instead of only the latest one,
+  populating `version` and `is_latest` of each entry. Only supported by versioned backends,
+  currently S3 versioned buckets. Defaults to `false`.
+- `max_keys::Option{Int}`: (Optional) The maximum number of entries to return.
+  The listing stops once this many entries were received, instead of buffering the whole result.
+- `start_after::Option{String}`: (Optional) Only return objects whose location sorts after this one.
+  Together with `max_keys` this allows paging through a listing, passing the location of the last
+  entry of a page to fetch the next one. Not supported together with `versions`.
+- `sorted::Bool`: (Optional) Sort the entries by location in the native library before returning them,
+  as backends differ in the order of listings. The entries are already held in memory, so sorting only
+  costs CPU time, but with `max_keys` only the entries of each page are sorted. Defaults to `false`.
+- `modified_since::Option{Int64}`: (Optional) Only return objects last modified after this time,
+  in milliseconds since the Unix epoch like `ObjectMeta` `last_modified`. The filter is applied by the
+  native library to the listing received from the server, as backends do not support it, so it reduces
+  the entries transferred to Julia but not the cost of the listing itself. Entries filtered out
+  do not count towards `max_keys`.
+
+# Returns
+- `entries::Vector{ObjectMeta}`: The metadata of the listed objects, in lexicographic order of location
+  if `start_after` is given.
+
+# Throws
+- `ListException`: If the request fails for any reason, including if `versions` is requested
+  but the backend does not support listing object versions.
+"""
+function list_objects(
+    prefix::String,
+    conf::AbstractConfig;
+    versions::Bool=false,
+    max_keys::Option{Int}=nothing,
+    start_after::Option{String}=nothing,
+    sorted::Bool=false,
+    modified_since::Option{Int64}=nothing
+)
+    if versions && !isnothing(start_after)
+        throw(ArgumentError("start_after is not supported when listing versions"))
+    end
+    if !isnothing(max_keys) && max_keys <= 0
+        throw(ArgumentError("max_keys must be positive, got $(max_keys)"))
+    end
+    # Zero and an empty offset mean no limit, no offset and no filter for the native library
+    limit = something(max_keys, 0)
+    offset = something(start_after, "")
+    min_last_modified = something(modified_since, 0)
+    response = ListResponseFFI()
+    ct = current_task()
+    event = Base.Event()
+    handle = pointer_from_objref(event)
+    config = into_config(conf)
+    while true
+        preserve_task(ct)
+        result = GC.@preserve config response event try
+            result = @ccall rust_lib.list(
+                prefix::Cstring,
+                versions::Cuchar,
+                limit::Culonglong,
+                offset::Cstring,
+                sorted::Cuchar,
+                min_last_modified::Clonglong,
+                config::Ref{Config},
+                response::Ref{ListResponseFFI},
+                handle::Ptr{Cvoid}
+            )::Cint
+
+            wait_or_cancel(event, response)
+
+            result
+        finally
+            unpreserve_task(ct)
+        end
+
+        if result == 2
+            # backoff
+            sleep(0.01)
+            continue
+        end
+
+        @throw_on_error(response, "list", ListException)
+
+        entries = convert_list_entries(response.entries)
+        @ccall rust_lib.destroy_list_entries(response.entries::ListEntriesFFI)::Cint
+
+        return entries
+    end
+end
+
+"""
+    prefix_size(prefix, conf) -> Tuple{Int, Int}
+
+Send a list request to the object store, aggregating the listed objects in the native library.
+
+Computes the total size of all objects with the given `prefix`, which is much cheaper than
+`list_objects` when the metadata of each object is not needed.
+
+# Arguments
+- `prefix::String`: Only objects with this prefix will be counted.
+- `conf::AbstractConfig`: The configuration to use for the request.
+  It includes credentials and other client options.
+
+# Returns
+- `(nbytes, count)::Tuple{Int, Int}`: The sum of the sizes in bytes of the objects with the given
+  `prefix`, and the number of those objects.
+
+# Throws
+- `ListException`: If the request fails for any reason.
+"""
+function prefix_size(prefix::String, conf::AbstractConfig)
+    response = PrefixSizeResponseFFI()
+    ct = current_task()
+    event = Base.Event()
+    handle = pointer_from_objref(event)
+    config = into_config(conf)
+    while true
+        preserve_task(ct)
+        result = GC.@preserve config response event try
+            result = @ccall rust_lib.prefix_size(
+                prefix::Cstring,
+                config::Ref{Config},
+                response::Ref{PrefixSizeResponseFFI},
+                handle::Ptr{Cvoid}
+            )::Cint
+
+            wait_or_cancel(event, response)
+
+            result
+        finally
+            unpreserve_task(ct)
+        end
+
+        if result == 2
+            # backoff
+            sleep(0.01)
+            continue
+        end
+
+        @throw_on_error(response, "prefix_size", ListException)
+
+        return (Int(response.length), Int(response.count))
+    end
+end
+
+"""
+    list_multipart_uploads(prefix, conf) -> Vector{MultipartUpload}
+
+Send a request to list the in-progress multipart uploads to the object store.
+
+Multipart uploads that are neither completed nor aborted, e.g. because the process uploading
+was killed, are not visible as objects but their parts still accumulate storage cost.
+
+# Arguments
+- `prefix::String`: Only uploads of objects with this prefix will be returned.
+- `conf::AbstractConfig`: The configuration to use for the request.
+  It includes credentials and other client options.
+
+# Returns
+- `uploads::Vector{MultipartUpload}`: The in-progress multipart uploads.
+
+# Throws
+- `ListException`: If the request fails for any reason, including if the backend does not support
+  listing multipart uploads (e.g. Azure, whose uncommitted blocks are discarded by the service),
+  in which case `is_not_supported` is true for the exception.
+"""
+function list_multipart_uploads(prefix::String, conf::AbstractConfig)
+    response = ListUploadsResponseFFI()
+    ct = current_task()
+    event = Base.Event()
+    handle = pointer_from_objref(event)
+    config = into_config(conf)
+    while true
+        preserve_task(ct)
+        result = GC.@preserve config response event try
+            result = @ccall rust_lib.list_multipart_uploads(
+                prefix::Cstring,
+                config::Ref{Config},
+                response::Ref{ListUploadsResponseFFI},
+                handle::Ptr{Cvoid}
+            )::Cint
+
+            wait_or_cancel(event, response)
+
+            result
+        finally
+            unpreserve_task(ct)
+        end
+
+        if result == 2
+            # backoff
+            sleep(0.01)
+            continue
+        end
+
+        @throw_on_error(response, "list_multipart_uploads", ListException)
 
-    Response() = new(-1, 0, C_NULL, C_NULL)
+        uploads = convert_multipart_uploads(response.uploads)
+        @ccall rust_lib.destroy_multipart_uploads(response.uploads::MultipartUploadsFFI)::Cint
+
+        return uploads
+    end
 end
 
-abstract type ErrorReason end
+"""
+    abort_stale_uploads(prefix, older_than_secs, conf) -> Int
 
-struct ConnectionError <: ErrorReason end
-struct StatusError <: ErrorReason
-    code::Int
-end
-struct EarlyEOF <: ErrorReason end
-struct TimeoutError <: ErrorReason end
-struct ParseURLError <: ErrorReason end
-struct UnknownError <: ErrorReason end
+Send requests to abort the in-progress multipart uploads to the object store that were
+initiated more than `older_than_secs` seconds ago.
 
-abstract type RequestException <: Exception end
-struct GetException <: RequestException
-    msg::String
-    reason::ErrorReason
+Uploads are listed and aborted in the native library, see `list_multipart_uploads`.
+Uploads still in use by a running process are aborted too if they are old enough, so
+`older_than_secs` should be well above the time any upload is expected to take.
 
-    GetException(msg) = new(msg, rust_message_to_reason(msg))
-end
-struct PutException <: RequestException
-    msg::String
-    reason::ErrorReason
+# Arguments
+- `prefix::String`: Only uploads of objects with this prefix will be aborted.
+- `older_than_secs::Int`: The min age in seconds of the uploads to abort.
+- `conf::AbstractConfig`: The configuration to use for the request.
+  It includes credentials and other client options.
 
-    PutException(msg) = new(msg, rust_message_to_reason(msg))
-end
-struct DeleteException <: RequestException
-    msg::String
-    reason::ErrorReason
+# Returns
+- `count::Int`: The number of aborted uploads.
 
-    DeleteException(msg) = new(msg, rust_message_to_reason(msg))
-end
+# Throws
+- `DeleteException`: If the request fails for any reason, including if the backend does not support
+  listing multipart uploads, in which case `is_not_supported` is true for the exception.
+"""
+function abort_stale_uploads(prefix::String, older_than_secs::Int, conf::AbstractConfig)
+    if older_than_secs < 0
+        throw(ArgumentError("older_than_secs must be non-negative, got $(older_than_secs)"))
+    end
+    response = AbortUploadsResponseFFI()
+    ct = current_task()
+    event = Base.Event()
+    handle = pointer_from_objref(event)
+    config = into_config(conf)
+    while true
+        preserve_task(ct)
+        result = GC.@preserve config response event try
+            result = @ccall rust_lib.abort_stale_uploads(
+                prefix::Cstring,
+                older_than_secs::Culonglong,
+                config::Ref{Config},
+                response::Ref{AbortUploadsResponseFFI},
+                handle::Ptr{Cvoid}
+            )::Cint
 
+            wait_or_cancel(event, response)
 
-function reason(e::GetException)
-    return e.reason::ErrorReason
-end
+            result
+        finally
+            unpreserve_task(ct)
+        end
 
-function reason(e::PutException)
-    return e.reason::ErrorReason
-end
+        if result == 2
+            # backoff
+            sleep(0.01)
+            continue
+        end
 
-function reason(e::DeleteException)
-    return e.reason::ErrorReason
-end
+        @throw_on_error(response, "abort_stale_uploads", DeleteException)
 
-function reason(e::Exception)
-    return UnknownError()
+        return Int(response.count)
+    end
 end
 
-function status_code(e::Exception)
-    return reason(e) isa StatusError ? reason(e).code : nothing
-end
+"""
+    head_object(path, conf) -> ObjectMeta
 
-function is_connection(e::Exception)
-    return reason(e) isa ConnectionError
-end
+Send a head request to the object store.
 
-function is_timeout(e::Exception)
-    return reason(e) isa TimeoutError
-end
+Fetches the metadata of the object at `path`, including its user-defined metadata
+and HTTP attributes, without transferring the object data.
 
-function is_early_eof(e::Exception)
-    return reason(e) isa EarlyEOF
-end
+# Arguments
+- `path::String`: The location of the object.
+- `conf::AbstractConfig`: The configuration to use for the request.
+  It includes credentials and other client options.
 
-function is_parse_url(e::Exception)
-    return reason(e) isa ParseURLError
-end
+# Returns
+- `meta::ObjectMeta`: The metadata of the object.
 
-function is_unknown(e::Exception)
-    return reason(e) isa UnknownError
-end
+# Throws
+- `HeadException`: If the request fails for any reason, including if the object does not exist.
+"""
+function head_object(path::String, conf::AbstractConfig)
+    response = HeadResponseFFI()
+    ct = current_task()
+    event = Base.Event()
+    handle = pointer_from_objref(event)
+    config = into_config(conf)
+    while true
+        preserve_task(ct)
+        result = GC.@preserve config response event try
+            result = @ccall rust_lib.head(
+                path::Cstring,
+                config::Ref{Config},
+                response::Ref{HeadResponseFFI},
+                handle::Ptr{Cvoid}
+            )::Cint
 
+            wait_or_cancel(event, response)
 
-function rust_message_to_reason(msg::AbstractString)
-    if (
-        contains(msg, "connection error")
-        || contains(msg, "tcp connect error")
-        || contains(msg, "error trying to connect")
-        || contains(msg, "client error (Connect)")
-       ) && (
-        contains(msg, "deadline has elapsed")
-        || contains(msg, "Connection refused")
-        || contains(msg, "Connection reset by peer")
-        || contains(msg, "dns error")
-       )
-        return ConnectionError()
-    elseif contains(msg, "Client error with status")
-        m = match(r"Client error with status (\d+) ", msg)
-        if !isnothing(m)
-            code = tryparse(Int, m.captures[1])
-            if !isnothing(code)
-                return StatusError(code)
-            else
-                return UnknownError()
-            end
-        else
-            return UnknownError()
+            result
+        finally
+            unpreserve_task(ct)
         end
-    elseif contains(msg, "HTTP status server error")
-        m = match(r"HTTP status server error \((\d+) ", msg)
-        if !isnothing(m)
-            code = tryparse(Int, m.captures[1])
-            if !isnothing(code)
-                return StatusError(code)
-            else
-                return UnknownError()
-            end
-        else
-            return UnknownError()
+
+        if result == 2
+            # backoff
+            sleep(0.01)
+            continue
         end
-    elseif contains(msg, "connection closed before message completed") ||
-        contains(msg, "end of file before message length reached") ||
-        contains(msg, "Connection reset by peer")
-        return EarlyEOF()
-    elseif contains(msg, "timed out")
-        return TimeoutError()
-    elseif contains(msg, "Unable to convert URL") ||
-        contains(msg, "Unable to recognise URL")
-        return ParseURLError()
-    else
-        return UnknownError()
+
+        @throw_on_error(response, "head", HeadException)
+
+        meta = convert_object_meta(
+            response.meta,
+            convert_key_values(response.metadata),
+            convert_key_values(response.attributes)
+        )
+        @ccall rust_lib.destroy_object_meta(response.meta::ObjectMetaFFI)::Cint
+        @ccall rust_lib.destroy_key_values(response.metadata::KeyValuesFFI)::Cint
+        @ccall rust_lib.destroy_key_values(response.attributes::KeyValuesFFI)::Cint
+
+        return meta
     end
 end
 
+struct HeadBatchEntryFFI
+    result::Cint
+    meta::ObjectMetaFFI
+    http_status::Cushort
+    error_message::Ptr{Cchar}
+
+    HeadBatchEntryFFI() = new(-1, ObjectMetaFFI(C_NULL, 0, 0, C_NULL, C_NULL, 0, 0), 0, C_NULL)
+end
+
 """
-    get_object!(buffer, path, conf) -> Int
+    head_objects(paths, conf) -> Vector{ObjectMeta}
 
-Send a get request to the object store.
+Send a batch of head requests to the object store.
 
-Fetches the data bytes at `path` and writes them to the given `buffer`.
+Fetches the metadata of the objects at each of the `paths`, e.g. to size the buffers of a large
+batch read. The requests are dispatched together and processed concurrently (up to the
+`StaticConfig` `concurrency_limit`), and the calling task is notified once after all of them complete.
 
 # Arguments
-- `buffer::AbstractVector{UInt8}`: The buffer to write the object data to.
-  The contents of the buffer will be mutated.
-  The buffer must be at least as large as the data.
-  The buffer will not be resized.
-- `path::String`: The location of the data to fetch.
-- `conf::AbstractConfig`: The configuration to use for the request.
+- `paths::AbstractVector{String}`: The locations of the objects.
+- `conf::AbstractConfig`: The configuration to use for the requests.
   It includes credentials and other client options.
 
 # Returns
-- `nbytes::Int`: The number of bytes read from the object store and written to the buffer.
-  That is, `buffer[1:nbytes]` will contain the object data.
+- `metas::Vector{ObjectMeta}`: The metadata of each object, in the order of `paths`.
+  Unlike `head_object`, the user-defined metadata and HTTP attributes are not populated.
 
 # Throws
-- `GetException`: If the request fails for any reason, including if the `buffer` is too small.
+- `HeadException`: If any of the requests fails for any reason, including if an object does not exist,
+  in which case `is_not_found` is true for the exception.
+  The message identifies the path of the first failed request.
 """
-function get_object!(buffer::AbstractVector{UInt8}, path::String, conf::AbstractConfig)
+function head_objects(paths::AbstractVector{String}, conf::AbstractConfig)
+    count = length(paths)
+    entries = [HeadBatchEntryFFI() for _ in 1:count]
     response = Response()
-    size = length(buffer)
     ct = current_task()
     event = Base.Event()
     handle = pointer_from_objref(event)
     config = into_config(conf)
+    path_ptrs = Cstring[Base.unsafe_convert(Cstring, path) for path in paths]
     while true
         preserve_task(ct)
-        result = GC.@preserve buffer config response event try
-            result = @ccall rust_lib.get(
-                path::Cstring,
-                buffer::Ref{Cuchar},
-                size::Culonglong,
+        result = GC.@preserve paths path_ptrs entries config response event try
+            result = @ccall rust_lib.head_batch(
+                path_ptrs::Ptr{Cstring},
+                count::Culonglong,
+                entries::Ptr{HeadBatchEntryFFI},
                 config::Ref{Config},
                 response::Ref{Response},
                 handle::Ptr{Cvoid}
@@ -737,49 +3351,75 @@ function get_object!(buffer::AbstractVector{UInt8}, path::String, conf::Abstract
             continue
         end
 
-        @throw_on_error(response, "get", GetException)
+        @throw_on_error(response, "head_batch", HeadException)
 
-        return Int(response.length)
+        # Reclaim all entries before throwing
+        errors = [take_cstring(entry.error_message) for entry in entries]
+        metas = Vector{Option{ObjectMeta}}(nothing, count)
+        for (i, entry) in enumerate(entries)
+            if entry.result == 0
+                metas[i] = convert_object_meta(entry.meta)
+                @ccall rust_lib.destroy_object_meta(entry.meta::ObjectMetaFFI)::Cint
+            end
+        end
+        for (path, entry, err) in zip(paths, entries, errors)
+            if entry.result != 0
+                msg = something(err, "unknown error")
+                throw(HeadException(
+                    string("failed to process head of ", repr(path), " with error: ", msg),
+                    Int(entry.http_status)
+                ))
+            end
+        end
+
+        return Vector{ObjectMeta}(metas)
     end
 end
 
+mutable struct TagsResponseFFI
+    result::Cint
+    tags::KeyValuesFFI
+    http_status::Cushort
+    error_message::Ptr{Cchar}
+    context::Ptr{Cvoid}
+
+    TagsResponseFFI() = new(-1, KeyValuesFFI(C_NULL, 0), 0, C_NULL, C_NULL)
+end
+
 """
-    put_object(buffer, path, conf) -> Int
+    get_object_tags(path, conf) -> Dict{String, String}
 
-Send a put request to the object store.
+Send a request to fetch the tags of an object to the object store.
 
-Atomically writes the data bytes in `buffer` to `path`.
+Tags are key/value pairs stored alongside the object but separately from its data and metadata,
+e.g. to drive retention policies. Only the tags are transferred, not the object data.
 
 # Arguments
-- `buffer::AbstractVector{UInt8}`: The data to write to the object store.
-  This buffer will not be mutated.
-- `path::String`: The location to write data to.
+- `path::String`: The location of the object.
 - `conf::AbstractConfig`: The configuration to use for the request.
   It includes credentials and other client options.
 
 # Returns
-- `nbytes::Int`: The number of bytes written to the object store.
-  Is always equal to `length(buffer)`.
+- `tags::Dict{String, String}`: The tag set of the object, empty if the object has no tags.
 
 # Throws
-- `PutException`: If the request fails for any reason.
+- `HeadException`: If the request fails for any reason, including if the object does not exist
+  or if the backend does not support object tags, in which case `is_not_supported` is true for
+  the exception.
 """
-function put_object(buffer::AbstractVector{UInt8}, path::String, conf::AbstractConfig)
-    response = Response()
-    size = length(buffer)
+function get_object_tags(path::String, conf::AbstractConfig)
+    response = TagsResponseFFI()
     ct = current_task()
     event = Base.Event()
     handle = pointer_from_objref(event)
     config = into_config(conf)
     while true
         preserve_task(ct)
-        result = GC.@preserve buffer config response event try
-            result = @ccall rust_lib.put(
+        result = GC.@preserve config response event try
+            result = @ccall rust_lib.get_tags(
                 path::Cstring,
-                buffer::Ref{Cuchar},
-                size::Culonglong,
                 config::Ref{Config},
-                response::Ref{Response},
+                response::Ref{TagsResponseFFI},
                 handle::Ptr{Cvoid}
             )::Cint
 
@@ -796,28 +3436,47 @@ function put_object(buffer::AbstractVector{UInt8}, path::String, conf::AbstractC
             continue
         end
 
-        @throw_on_error(response, "put", PutException)
+        @throw_on_error(response, "get_tags", HeadException)
 
-        return Int(response.length)
+        tags = convert_key_values(response.tags)
+        @ccall rust_lib.destroy_key_values(response.tags::KeyValuesFFI)::Cint
+
+        return tags
     end
 end
 
+mutable struct SizeResponseFFI
+    result::Cint
+    length::Culonglong
+    exists::Cuchar
+    http_status::Cushort
+    error_message::Ptr{Cchar}
+    context::Ptr{Cvoid}
+
+    SizeResponseFFI() = new(-1, 0, 0, 0, C_NULL, C_NULL)
+end
+
 """
-    delete_object(path, conf)
+    object_size(path, conf) -> Option{Int}
 
-Send a delete request to the object store.
+Send a head request to the object store, checking if the object at `path` exists.
+
+Unlike `head_object`, a missing object is not an error, so a single request answers both
+whether the object exists and how large it is.
 
 # Arguments
-- `path::String`: The location of the object to delete.
+- `path::String`: The location of the object.
 - `conf::AbstractConfig`: The configuration to use for the request.
   It includes credentials and other client options.
 
+# Returns
+- `size::Option{Int}`: The size in bytes of the object, or `nothing` if it does not exist.
+
 # Throws
-- `DeleteException`: If the request fails for any reason. Note that S3 will treat a delete request
-  to a non-existing object as a success, while Azure Blob will treat it as a 404 error.
+- `HeadException`: If the request fails for any other reason.
 """
-function delete_object(path::String, conf::AbstractConfig)
-    response = Response()
+function object_size(path::String, conf::AbstractConfig)
+    response = SizeResponseFFI()
     ct = current_task()
     event = Base.Event()
     handle = pointer_from_objref(event)
@@ -825,10 +3484,10 @@ function delete_object(path::String, conf::AbstractConfig)
     while true
         preserve_task(ct)
         result = GC.@preserve config response event try
-            result = @ccall rust_lib.delete(
+            result = @ccall rust_lib.size(
                 path::Cstring,
                 config::Ref{Config},
-                response::Ref{Response},
+                response::Ref{SizeResponseFFI},
                 handle::Ptr{Cvoid}
             )::Cint
 
@@ -845,9 +3504,9 @@ function delete_object(path::String, conf::AbstractConfig)
             continue
         end
 
-        @throw_on_error(response, "delete_object", DeleteException)
+        @throw_on_error(response, "size", HeadException)
 
-        return nothing
+        return response.exists == 0 ? nothing : Int(response.length)
     end
 end
 
@@ -855,20 +3514,22 @@ mutable struct ReadResponseFFI
     result::Cint
     length::Culonglong
     eof::Cuchar
+    http_status::Cushort
     error_message::Ptr{Cchar}
     context::Ptr{Cvoid}
 
-    ReadResponseFFI() = new(-1, 0, 0, C_NULL, C_NULL)
+    ReadResponseFFI() = new(-1, 0, 0, 0, C_NULL, C_NULL)
 end
 
 mutable struct ReadStreamResponseFFI
     result::Cint
     stream::Ptr{Nothing}
     object_size::Culonglong
+    http_status::Cushort
     error_message::Ptr{Cchar}
     context::Ptr{Cvoid}
 
-    ReadStreamResponseFFI() = new(-1, C_NULL, 0, C_NULL, C_NULL)
+    ReadStreamResponseFFI() = new(-1, C_NULL, 0, 0, C_NULL, C_NULL)
 end
 
 """
@@ -1021,7 +3682,7 @@ function Base.write(to::IO, from::ReadStream)
 end
 
 """
-    get_object_stream(path, conf; size_hint, decompress) -> ReadStream
+    get_object_stream(path, conf; size_hint, decompress, prefetch_bytes) -> ReadStream
 
 Send a get request to the object store returning a stream of object data.
 
@@ -1033,6 +3694,10 @@ Send a get request to the object store returning a stream of object data.
 # Keyword
 - `size_hint::Int`: (Optional) Expected size of the object (optimization for small objects).
 - `decompress::Option{String}`: (Optional) Compression algorithm to decode the response stream (supports gzip, deflate, zlib or zstd)
+- `prefetch_bytes::Int`: (Optional) Size in bytes of a bounded buffer that the native library keeps filling
+  with the next chunks of the object while Julia consumes the stream, so that downloading overlaps with
+  processing the data. The download pauses while the buffer is full. The default 0 means to only fetch
+  data when the stream is read.
 
 # Returns
 - `stream::ReadStream`: The stream of object data chunks.
@@ -1040,7 +3705,14 @@ Send a get request to the object store returning a stream of object data.
 # Throws
 - `GetException`: If the request fails for any reason.
 """
-function get_object_stream(path::String, conf::AbstractConfig; size_hint::Int=0, decompress::String="")
+function get_object_stream(
+    path::String,
+    conf::AbstractConfig;
+    size_hint::Int=0,
+    decompress::String="",
+    prefetch_bytes::Int=0
+)
+    prefetch_bytes < 0 && throw(ArgumentError("prefetch_bytes must be non-negative, got $(prefetch_bytes)"))
     response = ReadStreamResponseFFI()
     ct = current_task()
     event = Base.Event()
@@ -1054,6 +3726,7 @@ function get_object_stream(path::String, conf::AbstractConfig; size_hint::Int=0,
                 path::Cstring,
                 hint::Culonglong,
                 decompress::Cstring,
+                prefetch_bytes::Culonglong,
                 config::Ref{Config},
                 response::Ref{ReadStreamResponseFFI},
                 handle::Ptr{Cvoid}
@@ -1158,19 +3831,21 @@ end
 mutable struct WriteResponseFFI
     result::Cint
     length::Culonglong
+    http_status::Cushort
     error_message::Ptr{Cchar}
     context::Ptr{Cvoid}
 
-    WriteResponseFFI() = new(-1, 0, C_NULL, C_NULL)
+    WriteResponseFFI() = new(-1, 0, 0, C_NULL, C_NULL)
 end
 
 mutable struct WriteStreamResponseFFI
     result::Cint
     stream::Ptr{Nothing}
+    http_status::Cushort
     error_message::Ptr{Cchar}
     context::Ptr{Cvoid}
 
-    WriteStreamResponseFFI() = new(-1, C_NULL, C_NULL, C_NULL)
+    WriteStreamResponseFFI() = new(-1, C_NULL, 0, C_NULL, C_NULL)
 end
 
 """
@@ -1406,10 +4081,48 @@ function _unsafe_write(stream::WriteStream, input::Ptr{UInt8}, nbytes::Int; flus
     return Int(response.length)
 end
 
+"""
+    native_library_version() -> String
+
+Return the versions of the loaded object_store_ffi library and of the object_store crate it was built with,
+e.g. `"object_store_ffi 0.8.0 (object_store 0.10.1)"`.
+
+This can be used to check that the library in use matches expectations, e.g. the version of the
+installed `object_store_ffi_jll` package, or a local build set with `OBJECT_STORE_LIB`.
+"""
+function native_library_version()
+    # The string is static in the native library and must not be freed
+    return unsafe_string(@ccall rust_lib.version()::Cstring)
+end
+
+"""
+    $TYPEDEF
+
+Snapshot of the native library metrics.
+
+# Fields
+$TYPEDFIELDS
+"""
 struct Metrics
+    "The number of bytes currently allocated by the native library"
     live_bytes::Int64
+    """
+    The number of requests currently being processed.
+    When this stays at `concurrency_limit` requests are bound by concurrency rather than by the request queue.
+    """
+    in_flight_requests::Int64
+    """
+    The number of clients built since the runtime started, whether for a request or by `warmup_connections`.
+    Requests with a configuration whose client is in the client cache do not build a new one.
+    """
+    client_builds::Int64
 end
 
+"""
+    current_metrics() -> Metrics
+
+Return a snapshot of the native library metrics.
+"""
 function current_metrics()
     return @ccall rust_lib.current_metrics()::Metrics
 end
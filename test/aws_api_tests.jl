@@ -24,4 +24,42 @@
         secret_access_key="d",
         host="d"
     )) == "AWSConfig(region=\"a\", bucket_name=\"b\", access_key_id=*****, secret_access_key=*****, host=\"d\", opts=ClientOptions())"
+
+    # server-side encryption is applied to the config
+    conf = AWSConfig(;
+        region="a",
+        bucket_name="b",
+        access_key_id="c",
+        secret_access_key="d",
+        sse_algorithm="aws:kms",
+        sse_kms_key_id="e"
+    )
+    @test repr(conf) == "AWSConfig(region=\"a\", bucket_name=\"b\", access_key_id=*****, secret_access_key=*****, sse_algorithm=\"aws:kms\", sse_kms_key_id=\"e\", opts=ClientOptions())"
+    @test occursin("\"aws_server_side_encryption\":\"aws:kms\"", conf.cached_config.config_string)
+    @test occursin("\"aws_sse_kms_key_id\":\"e\"", conf.cached_config.config_string)
+
+    other = AWSConfig(conf; bucket_name="f")
+    @test repr(other) == "AWSConfig(region=\"a\", bucket_name=\"f\", access_key_id=*****, secret_access_key=*****, sse_algorithm=\"aws:kms\", sse_kms_key_id=\"e\", opts=ClientOptions())"
+
+    @test_throws ArgumentError AWSConfig(; region="a", bucket_name="b", sse_algorithm="aes")
+    @test_throws ArgumentError AWSConfig(; region="a", bucket_name="b", sse_algorithm="AES256", sse_kms_key_id="e")
+
+    # the path prefix is part of the connection
+    conf = AWSConfig(; region="a", bucket_name="b", path_prefix="partition=1")
+    @test repr(conf) == "AWSConfig(region=\"a\", bucket_name=\"b\", path_prefix=\"partition=1\", opts=ClientOptions())"
+    @test occursin("\"path_prefix\":\"partition=1\"", conf.cached_config.config_string)
+    @test AWSConfig(conf; bucket_name="c").path_prefix == "partition=1"
+end
+
+@testitem "AWSConfig connection_info" setup=[InitializeObjectStore] begin
+    conf = AWSConfig(;
+        region="eu-west-1",
+        bucket_name="b",
+        access_key_id="c",
+        secret_access_key="d"
+    )
+    info = connection_info(conf)
+    @test info["backend"] == "s3"
+    @test info["region"] == "eu-west-1"
+    @test occursin("eu-west-1", info["endpoint"])
 end
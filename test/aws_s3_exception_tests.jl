@@ -34,6 +34,8 @@
             catch err
                 @test err isa RustyObjectStore.GetException
                 @test occursin("Supplied buffer was too small", err.msg)
+                @test RustyObjectStore.is_buffer_too_small(err)
+                @test RustyObjectStore.required_buffer_size(err) > length(buffer)
             end
         end
 
@@ -82,6 +84,22 @@
             end
         end
 
+        @testset "Non-existing version" begin
+            buffer = Vector{UInt8}(undef, 100)
+            nbytes_written = put_object(codeunits("a,b,c"), "versioned.csv", config)
+            @assert nbytes_written == 5
+
+            try
+                # A well-formed version id that does not match any version of the object
+                get_object!(buffer, "versioned.csv", config; version="0f1e2d3c-4b5a-6978-8796-a5b4c3d2e1f0")
+                @test false # Should have thrown an error
+            catch e
+                @test e isa RustyObjectStore.GetException
+                @test occursin("404 Not Found", e.msg)
+                @test RustyObjectStore.status_code(e) == 404
+            end
+        end
+
         @testset "Delete non-existing file" begin
             # S3 semantics is to return success on deleting a non-existing file, so we expect this
             # to succeed
@@ -159,7 +177,8 @@ end # @testitem
 @testitem "AWS S3 retries" setup=[InitializeObjectStore] begin
     using CloudBase.CloudTest: Minio
     import CloudBase
-    using RustyObjectStore: get_object!, put_object, AWSConfig, ClientOptions, is_timeout, is_early_eof, status_code
+    using RustyObjectStore: get_object!, put_object, AWSConfig, ClientOptions, is_timeout, is_early_eof, status_code,
+        is_permission_denied, is_checksum_mismatch, is_throttled
     import HTTP
     import Sockets
 
@@ -395,10 +414,12 @@ end # @testitem
         return nrequests[]
     end
 
-    function test_status(method, response_status, headers=nothing)
+    function test_status(
+        method, response_status, headers=nothing;
+        response_body="response body from the dummy server", checksum_mismatch=false
+    )
         @assert method === :GET || method === :PUT
         nrequests = Ref(0)
-        response_body = "response body from the dummy server"
 
         (port, tcp_server) = Sockets.listenany(8081)
         http_server = HTTP.serve!(tcp_server) do request::HTTP.Request
@@ -433,7 +454,10 @@ end # @testitem
             method === :PUT && @test e isa RustyObjectStore.PutException
             @test occursin(string(response_status), e.msg)
             @test status_code(e) == response_status
-            response_status < 500 && (@test occursin("response body from the dummy server", e.msg))
+            @test is_permission_denied(e) == (response_status == 403)
+            @test is_checksum_mismatch(e) == checksum_mismatch
+            @test is_throttled(e) == (response_status in (429, 503))
+            response_status < 500 && (@test occursin(response_body, e.msg))
         finally
             close(http_server)
         end
@@ -557,6 +581,15 @@ end # @testitem
         @test nrequests == 1
     end
 
+    @testset "400: BadDigest" begin
+        # Returned when the Content-MD5 of an upload does not match the received data
+        nrequests = test_status(
+            :PUT, 400;
+            response_body="<Error><Code>BadDigest</Code></Error>", checksum_mismatch=true
+        )
+        @test nrequests == 1
+    end
+
     @testset "403: Forbidden" begin
         # Returned when you pass an invalid api-key.
         # See https://www.rfc-editor.org/rfc/rfc9110#status.403
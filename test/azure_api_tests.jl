@@ -32,4 +32,88 @@
         @test e isa ErrorException
         @test e.msg == "Should provide either a storage_account_key or a storage_sas_token"
     end
+
+    # other containers of the account can be targeted with the same settings
+    conf = AzureConfig(;
+        storage_account_name="a",
+        container_name="b",
+        storage_account_key="c",
+        opts=ClientOptions(; max_retries=1)
+    )
+    other = AzureConfig(conf; container_name="d")
+    @test other.container_name == "d"
+    @test other.storage_account_key == "c"
+    @test other.opts === conf.opts
+    @test other.cached_config.config_string != conf.cached_config.config_string
+
+    # emulator hosts keep the configured account and key
+    conf = AzureConfig(;
+        storage_account_name="myaccount",
+        container_name="b",
+        storage_account_key="c",
+        host="http://127.0.0.1:10010/myaccount/b/"
+    )
+    @test occursin("\"azurite_host\":\"http://127.0.0.1:10010/myaccount/b/\"", conf.cached_config.config_string)
+    @test occursin("\"azure_storage_account_name\":\"myaccount\"", conf.cached_config.config_string)
+    @test occursin("\"azure_storage_account_key\":\"c\"", conf.cached_config.config_string)
+
+    # managed identity requests are signed, selecting the identity by client id
+    conf = AzureConfig(;
+        storage_account_name="a",
+        container_name="b",
+        auth_mode=:managed_identity,
+        client_id="c"
+    )
+    @test repr(conf) == "AzureConfig(storage_account_name=\"a\", container_name=\"b\", auth_mode=:managed_identity, client_id=\"c\", opts=ClientOptions())"
+    @test !occursin("azure_skip_signature", conf.cached_config.config_string)
+    @test occursin("\"azure_client_id\":\"c\"", conf.cached_config.config_string)
+
+    # workload identity requires the federated token and the identity to exchange it for
+    conf = AzureConfig(;
+        storage_account_name="a",
+        container_name="b",
+        auth_mode=:workload_identity,
+        client_id="c",
+        tenant_id="d",
+        federated_token_file="/var/run/secrets/token"
+    )
+    @test occursin("\"azure_federated_token_file\":\"/var/run/secrets/token\"", conf.cached_config.config_string)
+    @test occursin("\"azure_tenant_id\":\"d\"", conf.cached_config.config_string)
+    @test_throws ArgumentError AzureConfig(;
+        storage_account_name="a",
+        container_name="b",
+        auth_mode=:workload_identity,
+        client_id="c"
+    )
+
+    # explicit credentials conflict with other auth modes
+    @test_throws ArgumentError AzureConfig(;
+        storage_account_name="a",
+        container_name="b",
+        storage_account_key="c",
+        auth_mode=:managed_identity
+    )
+    @test_throws ArgumentError AzureConfig(; storage_account_name="a", container_name="b", auth_mode=:foo)
+
+    # the path prefix is part of the connection
+    conf = AzureConfig(; storage_account_name="a", container_name="b", path_prefix="partition=1")
+    @test repr(conf) == "AzureConfig(storage_account_name=\"a\", container_name=\"b\", path_prefix=\"partition=1\", opts=ClientOptions())"
+    @test occursin("\"path_prefix\":\"partition=1\"", conf.cached_config.config_string)
+    @test AzureConfig(conf; container_name="c").path_prefix == "partition=1"
+end
+
+@testitem "AzureConfig connection_info" setup=[InitializeObjectStore] begin
+    conf = AzureConfig(;
+        storage_account_name="a",
+        container_name="b",
+        storage_account_key="c",
+        host="http://127.0.0.1:10000/a/b/"
+    )
+    info = connection_info(conf)
+    @test info["backend"] == "azure"
+    @test startswith(info["endpoint"], "http://127.0.0.1:10000/")
+    @test !("c" in values(info))
+
+    # An unsupported URL scheme has no backend to resolve
+    @test_throws ArgumentError connection_info(RustyObjectStore.Config("ftp://a/b", Dict{String, String}()))
 end
@@ -32,6 +32,8 @@
             catch err
                 @test err isa RustyObjectStore.GetException
                 @test occursin("Supplied buffer was too small", err.msg)
+                @test RustyObjectStore.is_buffer_too_small(err)
+                @test RustyObjectStore.required_buffer_size(err) > length(buffer)
             end
         end
 
@@ -52,6 +54,8 @@
             catch err
                 @test err isa RustyObjectStore.GetException
                 @test occursin("Supplied buffer was too small", err.msg)
+                # The object size is known from the multipart get
+                @test RustyObjectStore.required_buffer_size(err) == 20_000_000
             end
         end
 
@@ -85,6 +89,14 @@
                 @test occursin("400 Bad Request", e.msg)
                 @test occursin("Authentication information is not given in the correct format", e.msg)
             end
+
+            try
+                RustyObjectStore.check_connection(bad_config)
+                @test false # Should have thrown an error
+            catch e
+                @test e isa RustyObjectStore.CheckConnectionException
+                @test occursin("Authentication information is not given in the correct format", e.msg)
+            end
         end
 
         @testset "Non-existing file" begin
@@ -193,6 +205,14 @@
             @test e isa RustyObjectStore.GetException
             @test occursin("Connection refused", e.msg)
         end
+
+        try
+            RustyObjectStore.check_connection(_stale_config)
+            @test false # Should have thrown an error
+        catch e
+            @test e isa RustyObjectStore.CheckConnectionException
+            @test occursin("Connection refused", e.msg)
+        end
     end
 
     @testset "multiple start" begin
@@ -213,7 +233,8 @@ end # @testitem
 @testitem "BlobStorage retries" setup=[InitializeObjectStore] begin
     using CloudBase.CloudTest: Azurite
     import CloudBase
-    using RustyObjectStore: get_object!, put_object, AWSConfig, ClientOptions, is_timeout, is_early_eof, status_code
+    using RustyObjectStore: get_object!, put_object, AWSConfig, ClientOptions, is_timeout, is_early_eof, status_code,
+        is_permission_denied, is_checksum_mismatch, is_throttled
     import HTTP
     import Sockets
 
@@ -224,10 +245,12 @@ end # @testitem
     container = "mycontainer"
     shared_key_from_azurite = "Eby8vdM02xNOcqFlqUwJPLlmEtlCDXJ1OUzFT50uSRZ6IFsuFq2UVErCz4I6tq/K1SZFPTOtr/KBHBeksoGMGw=="
 
-    function test_status(method, response_status, headers=nothing)
+    function test_status(
+        method, response_status, headers=nothing;
+        response_body="response body from the dummy server", checksum_mismatch=false
+    )
         @assert method === :GET || method === :PUT
         nrequests = Ref(0)
-        response_body = "response body from the dummy server"
 
         (port, tcp_server) = Sockets.listenany(8081)
         http_server = HTTP.serve!(tcp_server) do request::HTTP.Request
@@ -261,7 +284,11 @@ end # @testitem
             method === :PUT && @test e isa RustyObjectStore.PutException
             @test occursin(string(response_status), e.msg)
             @test status_code(e) == response_status
-            response_status < 500 && (@test occursin("response body from the dummy server", e.msg))
+            @test is_permission_denied(e) == (response_status == 403)
+            @test !RustyObjectStore.is_already_exists(e)
+            @test is_checksum_mismatch(e) == checksum_mismatch
+            @test is_throttled(e) == (response_status in (429, 503))
+            response_status < 500 && (@test occursin(response_body, e.msg))
         finally
             close(http_server)
         end
@@ -605,6 +632,15 @@ end # @testitem
         @test nrequests == 1
     end
 
+    @testset "400: Md5Mismatch" begin
+        # Returned when the Content-MD5 of an upload does not match the received data
+        nrequests = test_status(
+            :PUT, 400;
+            response_body="<Error><Code>Md5Mismatch</Code></Error>", checksum_mismatch=true
+        )
+        @test nrequests == 1
+    end
+
     @testset "403: Forbidden" begin
         # Returned when you pass an invalid api-key.
         # See https://www.rfc-editor.org/rfc/rfc9110#status.403
@@ -638,6 +674,9 @@ end # @testitem
         @test nrequests == 1
         nrequests = test_status(:PUT, 409)
         @test nrequests == 1
+        # A body mentioning an existing object does not make it an already exists error
+        nrequests = test_status(:PUT, 409; response_body="The specified blob already exists.")
+        @test nrequests == 1
     end
 
     @testset "412: Precondition Failed" begin
@@ -709,6 +748,12 @@ end # @testitem
         @test nrequests == 1 + max_retries
         nrequests = test_status(:PUT, 503)
         @test nrequests == 1 + max_retries
+
+        # The retries of the connection can be overridden per request
+        nrequests = with_max_retries(() -> test_status(:GET, 503), 4)
+        @test nrequests == 1 + 4
+        nrequests = with_max_retries(() -> test_status(:PUT, 503), 0)
+        @test nrequests == 1
     end
 
     @testset "504: Gateway Timeout" begin
@@ -729,6 +774,44 @@ end # @testitem
         @test nrequests == 1 + max_retries
     end
 
+    @testset "Operation deadline" begin
+        (port, tcp_server) = Sockets.listenany(8081)
+        http_server = HTTP.serve!(tcp_server) do request::HTTP.Request
+            sleep(2)
+            return HTTP.Response(200, "response body from the dummy server")
+        end
+
+        baseurl = "http://127.0.0.1:$port/$account/$container/"
+        conf = AzureConfig(;
+            storage_account_name=account,
+            container_name=container,
+            storage_account_key=shared_key_from_azurite,
+            host=baseurl,
+            opts=ClientOptions(;
+                max_retries=10,
+                retry_timeout_secs=60,
+                request_timeout_secs,
+                operation_deadline_secs=3
+            )
+        )
+
+        try
+            # Retries of the timed out attempts would take far longer than the deadline
+            elapsed = @elapsed try
+                get_object!(zeros(UInt8, 5), "blob", conf)
+                @test false # Should have thrown an error
+            catch e
+                @test e isa RustyObjectStore.GetException
+                @test occursin("Operation deadline exceeded", e.msg)
+                @test is_timeout(e)
+            end
+            @test elapsed < 10
+        finally
+            close(http_server)
+        end
+        wait(http_server)
+    end
+
     @testset "TCP Closed" begin
         nrequests = test_tcp_error(:GET)
         @test nrequests == 1 + max_retries
@@ -763,3 +846,180 @@ end # @testitem
         @test nrequests == 1
     end
 end
+
+@testitem "BlobStorage Content-Encoding" setup=[InitializeObjectStore] begin
+    using RustyObjectStore: get_object!, AzureConfig, ClientOptions
+    using CodecZlib
+    import HTTP
+    import Sockets
+
+    account = "myaccount"
+    container = "mycontainer"
+    shared_key_from_azurite = "Eby8vdM02xNOcqFlqUwJPLlmEtlCDXJ1OUzFT50uSRZ6IFsuFq2UVErCz4I6tq/K1SZFPTOtr/KBHBeksoGMGw=="
+    input = "1,2,3,4,5,6,7,8,9,1\n" ^ 50
+    compressed = transcode(GzipCompressor, codeunits(input))
+
+    (port, tcp_server) = Sockets.listenany(8084)
+    http_server = HTTP.serve!(tcp_server) do request::HTTP.Request
+        return HTTP.Response(200, [
+            "Content-Length" => string(length(compressed)),
+            "Content-Encoding" => "gzip",
+            "Last-Modified" => "Tue, 15 Oct 2019 12:45:26 GMT",
+            "ETag" => "123",
+        ], compressed)
+    end
+
+    baseurl = "http://127.0.0.1:$port/$account/$container/"
+    conf = AzureConfig(;
+        storage_account_name=account,
+        container_name=container,
+        storage_account_key=shared_key_from_azurite,
+        host=baseurl,
+        opts=ClientOptions(; max_retries=0)
+    )
+
+    try
+        @testset "Stored bytes by default" begin
+            buffer = Vector{UInt8}(undef, 2 * length(input))
+            nbytes_read = get_object!(buffer, "blob", conf)
+            @test buffer[1:nbytes_read] == compressed
+        end
+
+        @testset "Decoded content" begin
+            buffer = Vector{UInt8}(undef, 2 * length(input))
+            nbytes_read = get_object!(buffer, "blob", conf; decode_content_encoding=true)
+            @test nbytes_read == length(input)
+            @test String(buffer[1:nbytes_read]) == input
+        end
+
+        @testset "Decoded content larger than buffer" begin
+            # Buffer fits the compressed object but not the decompressed data
+            buffer = Vector{UInt8}(undef, length(compressed))
+            try
+                get_object!(buffer, "blob", conf; decode_content_encoding=true)
+                @test false # Should have thrown an error
+            catch e
+                @test e isa RustyObjectStore.GetException
+                @test occursin("Supplied buffer was too small", e.msg)
+            end
+        end
+    finally
+        close(http_server)
+    end
+    wait(http_server)
+end
+
+@testitem "BlobStorage raw paths" setup=[InitializeObjectStore] begin
+    using RustyObjectStore: get_object!, AzureConfig, ClientOptions
+    import HTTP
+    import Sockets
+
+    account = "myaccount"
+    container = "mycontainer"
+    shared_key_from_azurite = "Eby8vdM02xNOcqFlqUwJPLlmEtlCDXJ1OUzFT50uSRZ6IFsuFq2UVErCz4I6tq/K1SZFPTOtr/KBHBeksoGMGw=="
+
+    targets = String[]
+    (port, tcp_server) = Sockets.listenany(8085)
+    http_server = HTTP.serve!(tcp_server) do request::HTTP.Request
+        push!(targets, request.target)
+        return HTTP.Response(200, [
+            "Content-Length" => "3",
+            "Last-Modified" => "Tue, 15 Oct 2019 12:45:26 GMT",
+            "ETag" => "123",
+        ], "abc")
+    end
+
+    baseurl = "http://127.0.0.1:$port/$account/$container/"
+    conf = AzureConfig(;
+        storage_account_name=account,
+        container_name=container,
+        storage_account_key=shared_key_from_azurite,
+        host=baseurl,
+        opts=ClientOptions(; max_retries=0)
+    )
+    requested_key(target) = HTTP.URIs.unescapeuri(first(split(target, '?')))
+
+    try
+        buffer = Vector{UInt8}(undef, 10)
+        @test get_object!(buffer, "dir/50%+1.csv", conf; raw_path=true) == 3
+        @test endswith(requested_key(last(targets)), "/dir/50%+1.csv")
+
+        # The default path encoding escapes `%`, so it requests a different key
+        @test get_object!(buffer, "dir/50%+1.csv", conf) == 3
+        @test !endswith(requested_key(last(targets)), "/dir/50%+1.csv")
+    finally
+        close(http_server)
+    end
+    wait(http_server)
+end
+
+@testitem "BlobStorage cancel_requests" setup=[InitializeObjectStore] begin
+    using RustyObjectStore: get_object!, cancel_requests, is_cancelled, AzureConfig, ClientOptions
+    import HTTP
+    import Sockets
+
+    account = "myaccount"
+    container = "mycontainer"
+    shared_key_from_azurite = "Eby8vdM02xNOcqFlqUwJPLlmEtlCDXJ1OUzFT50uSRZ6IFsuFq2UVErCz4I6tq/K1SZFPTOtr/KBHBeksoGMGw=="
+
+    nrequests = Threads.Atomic{Int}(0)
+    (port, tcp_server) = Sockets.listenany(8086)
+    http_server = HTTP.serve!(tcp_server) do request::HTTP.Request
+        Threads.atomic_add!(nrequests, 1)
+        sleep(10)
+        return HTTP.Response(200, "response body from the dummy server")
+    end
+
+    baseurl = "http://127.0.0.1:$port/$account/$container/"
+    conf = AzureConfig(;
+        storage_account_name=account,
+        container_name=container,
+        storage_account_key=shared_key_from_azurite,
+        host=baseurl,
+        opts=ClientOptions(; max_retries=0)
+    )
+
+    try
+        tasks = [Threads.@spawn get_object!(zeros(UInt8, 100), "blob$(i)", conf) for i in 1:3]
+        # Wait for the requests to reach the server before cancelling them
+        @test timedwait(() -> nrequests[] == 3, 5.0) == :ok
+        @test cancel_requests(conf) == 3
+        for t in tasks
+            try
+                fetch(t)
+                @test false # Should have thrown an error
+            catch e
+                e = e.task.exception
+                @test e isa RustyObjectStore.GetException
+                @test is_cancelled(e)
+            end
+        end
+        @test cancel_requests(conf) == 0
+    finally
+        close(http_server)
+    end
+    wait(http_server)
+end
+
+@testitem "BlobStorage client build failures" setup=[InitializeObjectStore] begin
+    using RustyObjectStore: warmup_connections, AzureConfig
+
+    mktempdir() do dir
+        # Building the client of this config reads the federated token file, which does not exist yet
+        token_file = joinpath(dir, "federated_token")
+        conf = AzureConfig(;
+            storage_account_name="myaccount",
+            container_name="mycontainer",
+            auth_mode=:workload_identity,
+            client_id="myclient",
+            tenant_id="mytenant",
+            federated_token_file=token_file
+        )
+
+        @test warmup_connections([conf]) == [false]
+        # Failed builds are not inserted into the client cache, so once the cause is fixed
+        # the client is built again, rather than an error remembered from the earlier attempt
+        write(token_file, "dummy-federated-token")
+        @test warmup_connections([conf]) == [true]
+    end
+end
@@ -1,12 +1,25 @@
 @testsetup module ReadWriteCases
 using RustyObjectStore: get_object!, put_object, get_object_stream, put_object_stream,
-    AbstractConfig, delete_object
+    AbstractConfig, delete_object, put_object_with_result, get_object_range!, get_object_ranges!,
+    list_objects, get_objects!, check_connection, get_object_from_url!, head_object, prefix_size,
+    get_object, get_object_to_file, put_object_from_file, object_size, copy_object, signed_url
 using CodecZlib
+using Dates: DateTime
+using JSON3
+import HTTP
+using Mmap
 using RustyObjectStore
 
 using Test: @testset, @test, @test_throws
 
 export run_read_write_test_cases, run_stream_test_cases, run_sanity_test_cases
+export run_conditional_put_test_cases
+
+# A callable object, to check that progress callbacks are not required to be functions
+struct ProgressRecorder
+    updates::Vector{Tuple{Int,Int}}
+end
+(recorder::ProgressRecorder)(transferred, total) = push!(recorder.updates, (transferred, total))
 
 function run_stream_test_cases(config::AbstractConfig)
     # ReadStream
@@ -59,6 +72,18 @@ function run_stream_test_cases(config::AbstractConfig)
         end
 
         close(ioobj)
+
+        # The same data is read when the download runs ahead of the reads
+        ioobj = get_object_stream("test.csv", config; prefetch_bytes=4 * N)
+        i = 1
+        while i < sizeof(multicsv)
+            nb = i + N > length(multicsv) ? length(multicsv) - i : N
+            readbytes!(ioobj, buf, N)
+            @test view(buf, 1:nb) == view(codeunits(multicsv), i:i+nb-1)
+            i += N
+        end
+
+        close(ioobj)
     end
     @testset "ReadStream small unsafe_read" begin
         multicsv = "1,2,3,4,5,6,7,8,9,1\n"^10; # 200 B
@@ -414,6 +439,18 @@ function run_read_write_test_cases(read_config::AbstractConfig, write_config::Ab
         @test nbytes_read == 0
     end
 
+    @testset "0B file, 20MB buffer" begin
+        # Buffer is over the multipart get threshold, so the empty object is fetched through
+        # the multipart path which must not issue any range requests
+        buffer = Vector{UInt8}(undef, 20_000_000)
+
+        nbytes_written = put_object(codeunits(""), "empty.csv", write_config)
+        @test nbytes_written == 0
+
+        nbytes_read = get_object!(buffer, "empty.csv", read_config)
+        @test nbytes_read == 0
+    end
+
     @testset "100B file, 100B buffer" begin
         input = "1,2,3,4,5,6,7,8,9,1\n" ^ 5
         buffer = Vector{UInt8}(undef, 100)
@@ -455,6 +492,141 @@ function run_read_write_test_cases(read_config::AbstractConfig, write_config::Ab
         @test String(buffer[1:nbytes_read]) == input
     end
 
+    @testset "put_object_with_result" begin
+        input = "1,2,3,4,5,6,7,8,9,1\n" ^ 5
+        buffer = Vector{UInt8}(undef, 100)
+
+        result = put_object_with_result(codeunits(input), "test100B.csv", write_config)
+        @test result.nbytes == 100
+        @test !isnothing(result.e_tag)
+
+        # A write of new content to the same path is assigned a different ETag
+        # (S3 ETags are the MD5 of the content, so identical content would keep the ETag)
+        input2 = "9,8,7,6,5,4,3,2,1,0\n" ^ 5
+        result2 = put_object_with_result(codeunits(input2), "test100B.csv", write_config)
+        @test result2.nbytes == 100
+        @test result2.e_tag != result.e_tag
+
+        nbytes_read = get_object!(buffer, "test100B.csv", read_config)
+        @test nbytes_read == 100
+        @test String(buffer[1:nbytes_read]) == input2
+
+        # Integrity checked uploads
+        result3 = put_object_with_result(codeunits(input), "test100B.csv", write_config; content_md5=true)
+        @test result3.nbytes == 100
+
+        # Reads conditional on the ETag of the last write. The stale ETag is the one of `input2`,
+        # as the last write has the same content as the first one and so may have the same ETag.
+        nbytes_read = get_object!(buffer, "test100B.csv", read_config; if_match=result3.e_tag)
+        @test nbytes_read == 100
+        @test result2.e_tag != result3.e_tag
+        try
+            get_object!(buffer, "test100B.csv", read_config; if_match=result2.e_tag)
+            @test false # Should have thrown an error
+        catch e
+            @test e isa RustyObjectStore.GetException
+            @test RustyObjectStore.is_precondition_failed(e)
+        end
+
+        # Read-after-write verification of the put
+        result3 = put_object_with_result(codeunits(input), "test100B.csv", write_config; verify=true)
+        @test result3.nbytes == 100
+        @test !isnothing(result3.e_tag)
+    end
+
+    @testset "get_object_range!" begin
+        input = "0123456789" ^ 10
+        @assert sizeof(input) == 100
+
+        nbytes_written = put_object(codeunits(input), "test100B.csv", write_config)
+        @test nbytes_written == 100
+
+        buffer = Vector{UInt8}(undef, 10)
+        nbytes_read = get_object_range!(buffer, "test100B.csv", read_config; offset=15)
+        @test nbytes_read == 10
+        @test String(buffer[1:nbytes_read]) == input[16:25]
+
+        # Range past the end of the object is truncated
+        nbytes_read = get_object_range!(buffer, "test100B.csv", read_config; offset=95)
+        @test nbytes_read == 5
+        @test String(buffer[1:nbytes_read]) == input[96:100]
+
+        # Suffix range
+        buffer = Vector{UInt8}(undef, 20)
+        nbytes_read = get_object_range!(buffer, "test100B.csv", read_config; suffix_length=8)
+        @test nbytes_read == 8
+        @test String(buffer[1:nbytes_read]) == input[93:100]
+
+        # Suffix longer than the object returns the whole object
+        buffer = Vector{UInt8}(undef, 200)
+        nbytes_read = get_object_range!(buffer, "test100B.csv", read_config; suffix_length=150)
+        @test nbytes_read == 100
+        @test String(buffer[1:nbytes_read]) == input
+    end
+
+    @testset "get_object_ranges!" begin
+        input = "0123456789" ^ 10
+
+        nbytes_written = put_object(codeunits(input), "test100B.csv", write_config)
+        @test nbytes_written == 100
+
+        buffers = [Vector{UInt8}(undef, n) for n in (10, 5, 1, 20)]
+        nbytes_read = get_object_ranges!(buffers, "test100B.csv", read_config; offsets=[0, 42, 99, 50])
+        @test nbytes_read == [10, 5, 1, 20]
+        @test String(buffers[1]) == "0123456789"
+        @test String(buffers[2]) == "23456"
+        @test String(buffers[3]) == "9"
+        @test String(buffers[4]) == "01234567890123456789"
+
+        @test_throws ArgumentError get_object_ranges!(buffers, "test100B.csv", read_config; offsets=[0])
+        @test_throws RustyObjectStore.GetException get_object_ranges!(
+            [Vector{UInt8}(undef, 10)], "test100B.csv", read_config; offsets=[95]
+        )
+    end
+
+    @testset "signed_url" begin
+        input = "1,2,3,4,5,6,7,8,9,1\n" ^ 5
+
+        # Upload and download through presigned URLs, without credentials
+        url = signed_url("signed.csv", write_config; method=:PUT, expires_in_secs=60)
+        # Azure requires the blob type of uploads, which is not a header of the signature
+        headers = write_config isa AzureConfig ? ["x-ms-blob-type" => "BlockBlob"] : Pair{String,String}[]
+        response = HTTP.put(url, headers, input)
+        @test response.status == (write_config isa AzureConfig ? 201 : 200)
+
+        url = signed_url("signed.csv", write_config)
+        response = HTTP.get(url)
+        @test String(response.body) == input
+
+        delete_object("signed.csv", write_config)
+        @test_throws ArgumentError signed_url("signed.csv", write_config; method=:DELETE)
+    end
+
+    @testset "copy_object" begin
+        input = "1,2,3,4,5,6,7,8,9,1\n" ^ 5
+        buffer = Vector{UInt8}(undef, 100)
+
+        nbytes_written = put_object(codeunits(input), "test100B.csv", write_config)
+        @test nbytes_written == 100
+
+        copy_object("test100B.csv", "copy.csv", write_config)
+        nbytes_read = get_object!(buffer, "copy.csv", read_config)
+        @test nbytes_read == 100
+        @test String(buffer[1:nbytes_read]) == input
+
+        delete_object("copy.csv", write_config)
+        @test_throws RustyObjectStore.CopyException copy_object("doesnt_exist.csv", "copy.csv", write_config)
+
+        # Copies between configurations stream the data through the native library
+        @test copy_object("test100B.csv", read_config, "copy.csv", write_config) == 100
+        nbytes_read = get_object!(buffer, "copy.csv", read_config)
+        @test nbytes_read == 100
+        @test String(buffer[1:nbytes_read]) == input
+
+        delete_object("copy.csv", write_config)
+        @test_throws RustyObjectStore.CopyException copy_object("doesnt_exist.csv", read_config, "copy.csv", write_config)
+    end
+
     @testset "delete_object" begin
         input = "1,2,3,4,5,6,7,8,9,1\n" ^ 5
         buffer = Vector{UInt8}(undef, 100)
@@ -472,7 +644,284 @@ function run_read_write_test_cases(read_config::AbstractConfig, write_config::Ab
         catch e
             @test e isa RustyObjectStore.GetException
             @test occursin("not found", e.msg)
+            # The status is reported by the native library even though the message omits it
+            @test status_code(e) == 404
+        end
+
+        # Deleting again succeeds on all backends when not found objects are ignored
+        @test isnothing(delete_object("test100B.csv", write_config; ignore_not_found=true))
+    end
+
+    @testset "get_object! decompress" begin
+        input = "1,2,3,4,5,6,7,8,9,1\n" ^ 100
+        compressed = transcode(GzipCompressor, codeunits(input))
+
+        nbytes_written = put_object(compressed, "test.csv.gz", write_config)
+        @test nbytes_written == length(compressed)
+
+        buffer = Vector{UInt8}(undef, 2 * length(input))
+        nbytes_read = get_object!(buffer, "test.csv.gz", read_config; decompress="gzip")
+        @test nbytes_read == length(input)
+        @test String(buffer[1:nbytes_read]) == input
+
+        # Stored bytes are returned without decompression
+        nbytes_read = get_object!(buffer, "test.csv.gz", read_config)
+        @test buffer[1:nbytes_read] == compressed
+
+        @test_throws ArgumentError get_object!(buffer, "test.csv.gz", read_config; decompress="lz4")
+        @test_throws ArgumentError get_object!(
+            buffer, "test.csv.gz", read_config; decode_content_encoding=true, decompress="gzip"
+        )
+        delete_object("test.csv.gz", write_config)
+    end
+
+    @testset "get_object" begin
+        input = "1,2,3,4,5,6,7,8,9,1\n" ^ 5
+
+        nbytes_written = put_object(codeunits(input), "test100B.csv", write_config)
+        @test nbytes_written == 100
+
+        data = get_object("test100B.csv", read_config)
+        @test length(data) == 100
+        @test String(data) == input
+
+        # Empty objects return an empty buffer
+        nbytes_written = put_object(UInt8[], "test0B.csv", write_config)
+        @test nbytes_written == 0
+        @test isempty(get_object("test0B.csv", read_config))
+
+        delete_object("test0B.csv", write_config)
+        @test_throws RustyObjectStore.GetException get_object("test0B.csv", read_config)
+    end
+
+    @testset "get_object_to_file" begin
+        input = "1,2,3,4,5,6,7,8,9,1\n" ^ 5
+
+        nbytes_written = put_object(codeunits(input), "test100B.csv", write_config)
+        @test nbytes_written == 100
+
+        file_path = tempname()
+        nbytes_read = get_object_to_file("test100B.csv", file_path, read_config)
+        @test nbytes_read == 100
+        @test read(file_path, String) == input
+
+        # Existing files are truncated
+        write(file_path, "x" ^ 200)
+        nbytes_read = get_object_to_file("test100B.csv", file_path, read_config)
+        @test nbytes_read == 100
+        @test read(file_path, String) == input
+        rm(file_path)
+
+        @test_throws RustyObjectStore.GetException get_object_to_file("doesnt_exist.csv", file_path, read_config)
+    end
+
+    @testset "put_object_from_file" begin
+        input = "1,2,3,4,5,6,7,8,9,1\n" ^ 5
+        file_path = tempname()
+        write(file_path, input)
+
+        nbytes_written = put_object_from_file(file_path, "test100B.csv", write_config)
+        @test nbytes_written == 100
+
+        buffer = Vector{UInt8}(undef, 100)
+        nbytes_read = get_object!(buffer, "test100B.csv", read_config)
+        @test nbytes_read == 100
+        @test String(buffer[1:nbytes_read]) == input
+        rm(file_path)
+
+        @test_throws RustyObjectStore.PutException put_object_from_file(file_path, "test100B.csv", write_config)
+    end
+
+    @testset "get_object_from_url!" begin
+        input = "1,2,3,4,5,6,7,8,9,1\n" ^ 5
+        buffer = Vector{UInt8}(undef, 100)
+
+        nbytes_written = put_object(codeunits(input), "url/test100B.csv", write_config)
+        @test nbytes_written == 100
+
+        params = JSON3.read(RustyObjectStore.into_config(read_config).config_string, Dict{String, String})
+        url = pop!(params, "url") * "url/test100B.csv"
+        nbytes_read = get_object_from_url!(buffer, url, params)
+        @test nbytes_read == 100
+        @test String(buffer[1:nbytes_read]) == input
+
+        try
+            get_object_from_url!(buffer, "notascheme://container/url/test100B.csv", params)
+            @test false # should throw
+        catch e
+            @test e isa RustyObjectStore.GetException
+            @test RustyObjectStore.is_parse_url(e)
+        end
+
+        delete_object("url/test100B.csv", write_config)
+    end
+
+    @testset "get_objects!" begin
+        inputs = ["$(i),2,3,4,5,6,7,8,9,1\n" ^ i for i in 1:5]
+        paths = ["batch/test$(i).csv" for i in 1:5]
+        for (input, path) in zip(inputs, paths)
+            nbytes_written = put_object(codeunits(input), path, write_config)
+            @test nbytes_written == sizeof(input)
+        end
+
+        buffers = [Vector{UInt8}(undef, 100) for _ in 1:5]
+        nbytes_read = get_objects!(buffers, paths, read_config)
+        @test nbytes_read == sizeof.(inputs)
+        for (buffer, n, input) in zip(buffers, nbytes_read, inputs)
+            @test String(buffer[1:n]) == input
+        end
+
+        @test isempty(get_objects!(Vector{UInt8}[], String[], read_config))
+
+        try
+            get_objects!(buffers, [paths[1:4]; "batch/doesnt_exist.csv"], read_config)
+            @test false # should throw
+        catch e
+            @test e isa RustyObjectStore.GetException
+            @test occursin("batch/doesnt_exist.csv", e.msg)
+            @test status_code(e) == 404
+        end
+
+        # An undersized buffer fails its entry instead of reporting a partial read
+        small_buffers = [Vector{UInt8}(undef, 100) for _ in 1:4]
+        push!(small_buffers, Vector{UInt8}(undef, 10))
+        try
+            get_objects!(small_buffers, paths, read_config)
+            @test false # should throw
+        catch e
+            @test e isa RustyObjectStore.GetException
+            @test occursin("batch/test5.csv", e.msg)
+            @test is_buffer_too_small(e)
+            @test required_buffer_size(e) == sizeof(inputs[5])
+        end
+
+        for path in paths
+            delete_object(path, write_config)
+        end
+    end
+
+    @testset "head_object" begin
+        input = "1,2,3,4,5,6,7,8,9,1\n" ^ 5
+        result = put_object_with_result(codeunits(input), "head.csv", write_config)
+        @test result.nbytes == 100
+
+        meta = head_object("head.csv", read_config)
+        @test meta.location == "head.csv"
+        @test meta.size == 100
+        @test meta.e_tag == result.e_tag
+        @test meta.last_modified > 0
+        @test meta.has_last_modified
+        @test last_modified_datetime(meta) > DateTime(2020)
+        @test meta.metadata == Dict{String, String}()
+        @test !haskey(meta.attributes, "Cache-Control")
+
+        # HTTP attributes set on upload round-trip
+        put_object(
+            codeunits(input), "head.csv", write_config;
+            cache_control="max-age=3600", content_disposition="attachment; filename=\"data.csv\""
+        )
+        meta = head_object("head.csv", read_config)
+        @test meta.attributes["Cache-Control"] == "max-age=3600"
+        @test meta.attributes["Content-Disposition"] == "attachment; filename=\"data.csv\""
+
+        try
+            head_object("doesnt_exist.csv", read_config)
+            @test false # should throw
+        catch e
+            @test e isa RustyObjectStore.HeadException
+            @test status_code(e) == 404
+        end
+
+        @test object_size("head.csv", read_config) == 100
+        @test isnothing(object_size("doesnt_exist.csv", read_config))
+
+        metas = head_objects(["head.csv", "head.csv"], read_config)
+        @test length(metas) == 2
+        @test all(m -> m.location == "head.csv" && m.size == 100, metas)
+        @test isempty(head_objects(String[], read_config))
+        try
+            head_objects(["head.csv", "doesnt_exist.csv"], read_config)
+            @test false # Should have thrown an error
+        catch e
+            @test e isa RustyObjectStore.HeadException
+            @test is_not_found(e)
+        end
+
+        @test get_object_tags("head.csv", write_config) == Dict{String, String}()
+        try
+            get_object_tags("doesnt_exist.csv", write_config)
+            @test false # should throw
+        catch e
+            @test e isa RustyObjectStore.HeadException
+            @test status_code(e) == 404
+        end
+
+        delete_object("head.csv", write_config)
+    end
+
+    @testset "list_objects" begin
+        input = "1,2,3,4,5,6,7,8,9,1\n" ^ 5
+        for i in 1:3
+            nbytes_written = put_object(codeunits(input), "list/test$(i).csv", write_config)
+            @test nbytes_written == 100
+        end
+        nbytes_written = put_object(codeunits(input), "other/test.csv", write_config)
+        @test nbytes_written == 100
+
+        entries = list_objects("list/", write_config)
+        @test sort(map(e -> e.location, entries)) == ["list/test1.csv", "list/test2.csv", "list/test3.csv"]
+        @test all(e -> e.size == 100, entries)
+        @test all(e -> e.last_modified > 0, entries)
+        @test all(e -> e.is_latest, entries)
+        @test all(e -> isnothing(e.metadata), entries)
+
+        @test isempty(list_objects("doesnt_exist/", write_config))
+
+        entries = list_objects("list/", write_config; sorted=true)
+        @test map(e -> e.location, entries) == ["list/test1.csv", "list/test2.csv", "list/test3.csv"]
+
+        # Incremental listing of objects modified after the latest one
+        latest = maximum(e -> e.last_modified, entries)
+        @test isempty(list_objects("list/", write_config; modified_since=latest))
+        @test length(list_objects("list/", write_config; modified_since=latest - 60_000)) == 3
+
+        # Paging through the listing
+        page = list_objects("list/", write_config; max_keys=2, start_after="list/test1.csv")
+        @test map(e -> e.location, page) == ["list/test2.csv", "list/test3.csv"]
+        page = list_objects("list/", write_config; max_keys=2)
+        @test length(page) == 2
+        page = list_objects("list/", write_config; max_keys=2, start_after=last(page).location)
+        @test length(page) == 1
+        @test isempty(list_objects("list/", write_config; start_after="list/test3.csv"))
+
+        @test prefix_size("list/", write_config) == (300, 3)
+        @test prefix_size("doesnt_exist/", write_config) == (0, 0)
+
+        for i in 1:3
+            delete_object("list/test$(i).csv", write_config)
+        end
+        delete_object("other/test.csv", write_config)
+    end
+
+    @testset "put_object from memory-mapped file" begin
+        input = "1,2,3,4,5,6,7,8,9,1\n" ^ 50_000
+        (path, io) = mktemp()
+        write(io, input)
+        close(io)
+
+        nbytes_written = open(path, "r") do io
+            mapped = Mmap.mmap(io, Vector{UInt8}, sizeof(input))
+            put_object(mapped, "mmap.csv", write_config)
         end
+        @test nbytes_written == 1_000_000
+
+        buffer = Vector{UInt8}(undef, 1_000_000)
+        nbytes_read = get_object!(buffer, "mmap.csv", read_config)
+        @test nbytes_read == 1_000_000
+        @test String(buffer[1:nbytes_read]) == input
+
+        delete_object("mmap.csv", write_config)
+        rm(path)
     end
 
     # Large files should use multipart upload / download requests
@@ -502,6 +951,68 @@ function run_read_write_test_cases(read_config::AbstractConfig, write_config::Ab
         @test String(buffer[1:nbytes_read]) == input
     end
 
+    @testset "20MB file from and to local files" begin
+        input = "1,2,3,4,5,6,7,8,9,1\n" ^ 1_000_000
+        file_path = tempname()
+        write(file_path, input)
+
+        nbytes_written = put_object_from_file(file_path, "test100B.csv", write_config)
+        @test nbytes_written == 20_000_000
+
+        nbytes_read = get_object_to_file("test100B.csv", file_path, read_config)
+        @test nbytes_read == 20_000_000
+        @test read(file_path, String) == input
+        rm(file_path)
+    end
+
+    @testset "20MB file with progress" begin
+        input = "1,2,3,4,5,6,7,8,9,1\n" ^ 1_000_000
+        buffer = Vector{UInt8}(undef, 20_000_000)
+
+        updates = Tuple{Int,Int}[]
+        nbytes_written = put_object(
+            codeunits(input), "test100B.csv", write_config;
+            progress=(transferred, total) -> push!(updates, (transferred, total))
+        )
+        @test nbytes_written == 20_000_000
+        @test !isempty(updates)
+        @test issorted(first.(updates))
+        @test last(updates) == (20_000_000, 20_000_000)
+
+        empty!(updates)
+        nbytes_read = get_object!(
+            buffer, "test100B.csv", read_config;
+            progress=(transferred, total) -> push!(updates, (transferred, total))
+        )
+        @test nbytes_read == 20_000_000
+        @test String(buffer[1:nbytes_read]) == input
+        @test !isempty(updates)
+        @test issorted(first.(updates))
+        @test last(updates) == (20_000_000, 20_000_000)
+
+        recorder = ProgressRecorder(Tuple{Int,Int}[])
+        nbytes_read = get_object!(buffer, "test100B.csv", read_config; progress=recorder)
+        @test nbytes_read == 20_000_000
+        @test last(recorder.updates) == (20_000_000, 20_000_000)
+    end
+
+    # Edge cases of splitting a multipart download, the test setup uses 8MB parts
+    @testset "Multiples of the multipart part size" begin
+        part_size = 8 * 1024 * 1024
+        for nbytes in (part_size - 1, part_size, part_size + 1, 2 * part_size)
+            input = rand(UInt8, nbytes)
+            nbytes_written = put_object(input, "test_parts.bin", write_config)
+            @test nbytes_written == nbytes
+
+            # The buffer is larger than a part, so requests may be routed to multipart gets
+            buffer = Vector{UInt8}(undef, 3 * part_size)
+            nbytes_read = get_object!(buffer, "test_parts.bin", read_config)
+            @test nbytes_read == nbytes
+            @test buffer[1:nbytes_read] == input
+        end
+        delete_object("test_parts.bin", write_config)
+    end
+
     @testset "1MB file, 20MB buffer" begin
         input = "1,2,3,4,5,6,7,8,9,1\n" ^ 50_000
 
@@ -517,7 +1028,183 @@ function run_read_write_test_cases(read_config::AbstractConfig, write_config::Ab
 
 end
 
+function run_conditional_put_test_cases(config::AbstractConfig)
+    @testset "put_object mode=:update" begin
+        input = "1,2,3,4,5,6,7,8,9,1\n" ^ 5
+        buffer = Vector{UInt8}(undef, 100)
+
+        result = put_object_with_result(codeunits(input), "cas.csv", config)
+        @test result.nbytes == 100
+
+        # Matching ETag succeeds and yields a new ETag
+        updated = put_object_with_result(
+            codeunits(reverse(input)), "cas.csv", config;
+            mode=:update, expected_etag=result.e_tag
+        )
+        @test updated.nbytes == 100
+        @test updated.e_tag != result.e_tag
+
+        # Stale ETag is rejected and the object is unchanged
+        try
+            put_object(codeunits(input), "cas.csv", config; mode=:update, expected_etag=result.e_tag)
+            @test false # should throw
+        catch e
+            @test e isa RustyObjectStore.PutException
+            @test RustyObjectStore.is_precondition_failed(e)
+        end
+        nbytes_read = get_object!(buffer, "cas.csv", config)
+        @test String(buffer[1:nbytes_read]) == reverse(input)
+
+        delete_object("cas.csv", config)
+    end
+
+    @testset "put_object mode=:create" begin
+        input = "1,2,3,4,5,6,7,8,9,1\n" ^ 5
+        buffer = Vector{UInt8}(undef, 100)
+
+        nbytes_written = put_object(codeunits(input), "create.csv", config; mode=:create)
+        @test nbytes_written == 100
+
+        try
+            put_object(codeunits(reverse(input)), "create.csv", config; mode=:create)
+            @test false # should throw
+        catch e
+            @test e isa RustyObjectStore.PutException
+            @test RustyObjectStore.is_already_exists(e)
+            @test !RustyObjectStore.is_precondition_failed(e)
+        end
+        nbytes_read = get_object!(buffer, "create.csv", config)
+        @test String(buffer[1:nbytes_read]) == input
+
+        delete_object("create.csv", config)
+    end
+
+    @testset "delete_object expected_etag" begin
+        input = "1,2,3,4,5,6,7,8,9,1\n" ^ 5
+        result = put_object_with_result(codeunits(input), "conditional_delete.csv", config)
+        replaced = put_object_with_result(codeunits(input), "conditional_delete.csv", config)
+
+        # Stale ETag is rejected and the object is kept
+        try
+            delete_object("conditional_delete.csv", config; expected_etag=result.e_tag)
+            @test false # should throw
+        catch e
+            @test e isa RustyObjectStore.DeleteException
+            @test RustyObjectStore.is_precondition_failed(e)
+        end
+        buffer = Vector{UInt8}(undef, 100)
+        @test get_object!(buffer, "conditional_delete.csv", config) == 100
+
+        delete_object("conditional_delete.csv", config; expected_etag=replaced.e_tag)
+        @test_throws RustyObjectStore.GetException get_object!(buffer, "conditional_delete.csv", config)
+    end
+
+    @testset "put_object invalid mode" begin
+        @test_throws ArgumentError put_object(codeunits("a"), "cas.csv", config; mode=:append)
+        @test_throws ArgumentError put_object(codeunits("a"), "cas.csv", config; mode=:update)
+        @test_throws ArgumentError put_object(codeunits("a"), "cas.csv", config; expected_etag="123")
+    end
+
+    @testset "copy_object overwrite=false" begin
+        input = "1,2,3,4,5,6,7,8,9,1\n" ^ 5
+        put_object(codeunits(input), "claim_src.csv", config)
+
+        copy_object("claim_src.csv", "claim.csv", config; overwrite=false)
+        try
+            copy_object("claim_src.csv", "claim.csv", config; overwrite=false)
+            @test false # Should have thrown an error
+        catch e
+            @test e isa RustyObjectStore.CopyException
+            @test RustyObjectStore.is_already_exists(e)
+        end
+
+        delete_object("claim.csv", config)
+        delete_object("claim_src.csv", config)
+    end
+end
+
 function run_sanity_test_cases(read_config::AbstractConfig, write_config::AbstractConfig = read_config)
+    @testset "Round trip with request tag" begin
+        input = "1,2,3,4,5,6,7,8,9,1\n"
+        buffer = Vector{UInt8}(undef, length(input))
+
+        # The tag is passed to the native library with the config of each request
+        function converted_tag()
+            config = RustyObjectStore.into_config(read_config)
+            tag, config_ref = Base.cconvert(Ref{RustyObjectStore.Config}, config)
+            return GC.@preserve tag unsafe_string(config_ref[].request_tag)
+        end
+
+        with_request_tag("job-42") do
+            @test RustyObjectStore.request_tag() == "job-42"
+            @test converted_tag() == "job-42"
+
+            nbytes_written = put_object(codeunits(input), "roundtrip.csv", write_config)
+            @test nbytes_written == length(input)
+
+            nbytes_read = get_object!(buffer, "roundtrip.csv", read_config)
+            @test nbytes_read == length(input)
+            @test String(buffer[1:nbytes_read]) == input
+        end
+        @test RustyObjectStore.request_tag() == ""
+        @test converted_tag() == ""
+    end
+
+    @testset "Round trip without client cache" begin
+        input = "1,2,3,4,5,6,7,8,9,1\n"
+        buffer = Vector{UInt8}(undef, length(input))
+        params = JSON3.read(RustyObjectStore.into_config(read_config).config_string, Dict{String, String})
+        params["cache_client"] = "false"
+        uncached_config = RustyObjectStore.Config(pop!(params, "url"), params)
+
+        nbytes_written = put_object(codeunits(input), "roundtrip.csv", write_config)
+        @test nbytes_written == length(input)
+
+        nbytes_read = get_object!(buffer, "roundtrip.csv", uncached_config)
+        @test nbytes_read == length(input)
+        @test String(buffer[1:nbytes_read]) == input
+    end
+
+    @testset "Round trip with registered config" begin
+        input = "1,2,3,4,5,6,7,8,9,1\n"
+        buffer = Vector{UInt8}(undef, length(input))
+        registered_read_config = register_config(read_config)
+        registered_write_config = register_config(write_config)
+        @test registered_read_config.connection_id != 0
+        @test register_config(registered_read_config) === registered_read_config
+
+        nbytes_written = put_object(codeunits(input), "roundtrip.csv", registered_write_config)
+        @test nbytes_written == length(input)
+
+        nbytes_read = get_object!(buffer, "roundtrip.csv", registered_read_config)
+        @test nbytes_read == length(input)
+        @test String(buffer[1:nbytes_read]) == input
+    end
+
+    @testset "current_metrics" begin
+        metrics = current_metrics()
+        @test metrics.in_flight_requests >= 0
+        @test metrics.live_bytes >= 0
+        @test metrics.client_builds > 0
+    end
+
+    @testset "check_connection" begin
+        @test isnothing(check_connection(write_config))
+        @test isnothing(check_connection(write_config; round_trip=true))
+
+        # A user agent unique to this test makes the config miss the client cache
+        params = JSON3.read(RustyObjectStore.into_config(read_config).config_string, Dict{String, String})
+        params["user_agent"] = "warmup-test-$(rand(UInt64))"
+        fresh_config = RustyObjectStore.Config(pop!(params, "url"), params)
+
+        builds = current_metrics().client_builds
+        @test warmup_connections([fresh_config]) == [true]
+        @test current_metrics().client_builds == builds + 1
+        # The first request uses the warmed up client instead of building one
+        @test isnothing(check_connection(fresh_config))
+        @test current_metrics().client_builds == builds + 1
+    end
+
     @testset "Round trip" begin
         input = "1,2,3,4,5,6,7,8,9,1\n"
         buffer = Vector{UInt8}(undef, length(input))
@@ -528,6 +1215,12 @@ function run_sanity_test_cases(read_config::AbstractConfig, write_config::Abstra
         nbytes_read = get_object!(buffer, "roundtrip.csv", read_config)
         @test nbytes_read == length(input)
         @test String(buffer[1:nbytes_read]) == input
+
+        # Empty objects round trip too, overwriting existing data
+        @test put_object(UInt8[], "roundtrip.csv", write_config) == 0
+        @test object_size("roundtrip.csv", read_config) == 0
+        @test get_object!(buffer, "roundtrip.csv", read_config) == 0
+        @test isempty(get_object("roundtrip.csv", read_config))
     end
 end
 end # @testsetup
@@ -551,6 +1244,42 @@ Azurite.with(; debug=true, public=false) do conf
 
     run_read_write_test_cases(config)
     run_stream_test_cases(config)
+    run_conditional_put_test_cases(config)
+
+    @testset "path_prefix" begin
+        prefixed_config = AzureConfig(;
+            storage_account_name=_credentials.auth.account,
+            container_name=_container.name,
+            storage_account_key=_credentials.auth.key,
+            host=base_url,
+            path_prefix="partition=1"
+        )
+        input = "1,2,3,4,5,6,7,8,9,1\n" ^ 5
+        buffer = Vector{UInt8}(undef, 100)
+
+        @test put_object(codeunits(input), "prefixed.csv", prefixed_config) == 100
+        @test get_object!(buffer, "partition=1/prefixed.csv", config) == 100
+        @test String(buffer) == input
+        @test [e.location for e in list_objects("", prefixed_config)] == ["prefixed.csv"]
+
+        delete_object("prefixed.csv", prefixed_config)
+        @test isnothing(object_size("partition=1/prefixed.csv", config))
+    end
+
+    @testset "put_object storage_class" begin
+        @test put_object(codeunits("a,b,c"), "storage_class.csv", config; storage_class="Cool") == 5
+        delete_object("storage_class.csv", config)
+    end
+
+    @testset "multipart uploads housekeeping" begin
+        try
+            list_multipart_uploads("housekeeping/", config)
+            @test false # Should have thrown an error
+        catch e
+            @test e isa RustyObjectStore.ListException
+            @test RustyObjectStore.is_not_supported(e)
+        end
+    end
 
     config_padded = AzureConfig(;
         storage_account_name=_credentials.auth.account * "  \n",
@@ -611,6 +1340,48 @@ Minio.with(; debug=true, public=false) do conf
     run_read_write_test_cases(config)
     run_stream_test_cases(config)
 
+    @testset "copy_object overwrite=false without copy_if_not_exists" begin
+        put_object(codeunits("a,b,c"), "claim_src.csv", config)
+        try
+            copy_object("claim_src.csv", "claim.csv", config; overwrite=false)
+            @test false # Should have thrown an error
+        catch e
+            @test e isa RustyObjectStore.CopyException
+            @test RustyObjectStore.is_not_supported(e)
+        end
+        delete_object("claim_src.csv", config)
+    end
+
+    @testset "put_object storage_class" begin
+        @test put_object(codeunits("a,b,c"), "storage_class.csv", config; storage_class="REDUCED_REDUNDANCY") == 5
+        delete_object("storage_class.csv", config)
+
+        # Minio only supports the STANDARD and REDUCED_REDUNDANCY classes
+        try
+            put_object(codeunits("a,b,c"), "storage_class.csv", config; storage_class="GLACIER")
+            @test false # Should have thrown an error
+        catch e
+            @test e isa RustyObjectStore.PutException
+            @test RustyObjectStore.status_code(e) == 400
+        end
+        @test isnothing(object_size("storage_class.csv", config))
+
+        nbytes_written = put_object(
+            codeunits("a,b,c"), "storage_class.csv", config;
+            storage_class="GLACIER", ignore_unsupported_storage_class=true
+        )
+        @test nbytes_written == 5
+        @test object_size("storage_class.csv", config) == 5
+        delete_object("storage_class.csv", config)
+    end
+
+    @testset "multipart uploads housekeeping" begin
+        @test isempty(list_multipart_uploads("housekeeping/", config))
+        # Nothing was uploaded under the prefix, let alone an hour ago
+        @test abort_stale_uploads("housekeeping/", 3600, config) == 0
+        @test_throws ArgumentError abort_stale_uploads("housekeeping/", -1, config)
+    end
+
     config_padded = AWSConfig(;
         region=default_region * " \n",
         bucket_name=_container.name * " \n",
@@ -0,0 +1,79 @@
+@testitem "ClientOptions" begin
+    using RustyObjectStore: ClientOptions, AzureConfig, AWSConfig
+
+    @test isempty(ClientOptions().params)
+
+    # The native library default user agent applies unless one is given
+    @test ClientOptions(; user_agent="my-service/1.0").params["user_agent"] == "my-service/1.0"
+    @test repr(ClientOptions()) == "ClientOptions()"
+
+    # HTTP connection pool and keep-alive settings are forwarded to the native client
+    opts = ClientOptions(;
+        pool_idle_timeout_secs=90,
+        pool_max_idle_per_host=16,
+        http2_keep_alive_interval_secs=30,
+        http2_keep_alive_timeout_secs=10,
+        http2_keep_alive_while_idle=true
+    )
+    @test opts.params["pool_idle_timeout"] == "90s"
+    @test opts.params["pool_max_idle_per_host"] == "16"
+    @test opts.params["http2_keep_alive_interval"] == "30s"
+    @test opts.params["http2_keep_alive_timeout"] == "10s"
+    @test opts.params["http2_keep_alive_while_idle"] == "true"
+
+    # Connection-level retries can be toggled independently of the retry budget
+    opts = ClientOptions(; max_retries=5, retry_timeout_secs=30, retry_on_connection_errors=false)
+    @test opts.params["max_retries"] == "5"
+    @test opts.params["retry_timeout_secs"] == "30"
+    @test opts.params["retry_on_connection_errors"] == "false"
+
+    # The operation deadline bounds the whole request, not only retries
+    @test ClientOptions(; operation_deadline_secs=60).params["operation_deadline"] == "60s"
+
+    # One-off clients bypass the client cache
+    @test !haskey(ClientOptions().params, "cache_client")
+    @test ClientOptions(; cache_client=false).params["cache_client"] == "false"
+
+    # Outbound requests can be rate limited per connection
+    @test !haskey(ClientOptions().params, "max_requests_per_sec")
+    @test ClientOptions(; max_requests_per_sec=100).params["max_requests_per_sec"] == "100"
+
+    # Host names can be pinned to specific addresses
+    opts = ClientOptions(; dns_overrides=Dict("myaccount.blob.core.windows.net" => "10.0.0.5"))
+    @test opts.params["dns_overrides"] == "{\"myaccount.blob.core.windows.net\":\"10.0.0.5\"}"
+
+    # Errors reported by the backend are only retried for the given kinds
+    opts = ClientOptions(; retry_error_kinds=[:not_found, :precondition, :not_found], error_retries=3)
+    @test opts.params["retry_error_kinds"] == "not_found,precondition"
+    @test opts.params["error_retries"] == "3"
+    @test_throws ArgumentError ClientOptions(; retry_error_kinds=[:not_found])
+    @test_throws ArgumentError ClientOptions(; retry_error_kinds=[:permission_denied], error_retries=3)
+
+    # TLS verification is only disabled when explicitly requested
+    @test !haskey(ClientOptions().params, "allow_invalid_certificates")
+    @test ClientOptions(; allow_invalid_certificates=true).params["allow_invalid_certificates"] == "true"
+    azure_conf = AzureConfig(;
+        storage_account_name="a",
+        container_name="b",
+        storage_account_key="c",
+        host="https://custom.host/a/b/"
+    )
+    @test !occursin("allow_invalid_certificates", azure_conf.cached_config.config_string)
+    aws_conf = AWSConfig(;
+        region="a",
+        bucket_name="b",
+        access_key_id="c",
+        secret_access_key="d",
+        host="https://custom.host"
+    )
+    @test !occursin("allow_invalid_certificates", aws_conf.cached_config.config_string)
+
+    # Private CAs can be trusted without disabling TLS verification
+    pem = "-----BEGIN CERTIFICATE-----\nMIIB\n-----END CERTIFICATE-----\n"
+    @test ClientOptions(; ca_cert_pem=pem).params["ca_cert_pem"] == pem
+    @test_throws ArgumentError ClientOptions(; ca_cert_pem="/etc/ssl/ca.pem")
+
+    # Plaintext HTTP can be allowed for endpoints that are not emulators
+    @test !haskey(ClientOptions().params, "allow_http")
+    @test ClientOptions(; allow_http=true).params["allow_http"] == "true"
+end
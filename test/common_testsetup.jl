@@ -6,6 +6,7 @@
         cache_ttl_secs=30 * 60,
         cache_tti_secs=5 * 60,
         multipart_put_threshold=8 * 1024 * 1024,
+        multipart_put_part_size=8 * 1024 * 1024,
         multipart_get_threshold=8 * 1024 * 1024,
         multipart_get_part_size=8 * 1024 * 1024,
         concurrency_limit=512
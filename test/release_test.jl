@@ -5,3 +5,44 @@
     using object_store_ffi_jll
     @test RustyObjectStore.rust_lib == object_store_ffi_jll.libobject_store_ffi
 end
+
+@testitem "native_library_version" begin
+    version = native_library_version()
+    @test startswith(version, "object_store_ffi ")
+    @test occursin("object_store ", version)
+    # The released library must support the StaticConfig layout of this package
+    @test RustyObjectStore.native_version_number() >= RustyObjectStore.MIN_NATIVE_VERSION
+end
+
+@testitem "object_store_started" setup=[InitializeObjectStore] begin
+    @test object_store_started()
+end
+
+@testitem "StaticConfig validation" setup=[InitializeObjectStore] begin
+    config(; kwargs...) = StaticConfig(;
+        n_threads=0,
+        cache_capacity=20,
+        cache_ttl_secs=30 * 60,
+        cache_tti_secs=5 * 60,
+        multipart_put_threshold=8 * 1024 * 1024,
+        multipart_get_threshold=8 * 1024 * 1024,
+        multipart_get_part_size=8 * 1024 * 1024,
+        concurrency_limit=512,
+        kwargs...
+    )
+    # Logged configs show every field sent to the native library
+    shown = repr(config(; adaptive_concurrency_min=8, disable_multipart_get=true))
+    @test occursin("adaptive_concurrency_min=8,", shown)
+    @test occursin("disable_multipart_get=true,", shown)
+    @test endswith(shown, "connection_build_timeout_secs=0)")
+
+    # The runtime is already started, so later calls have no effect, whatever their config
+    @test isnothing(init_object_store(config(; concurrency_limit=0)))
+    @test isnothing(init_object_store(config()))
+
+    # Invalid configs are rejected before the runtime is started
+    @test isnothing(RustyObjectStore.invalid_config_message(config()))
+    @test !isnothing(RustyObjectStore.invalid_config_message(config(; concurrency_limit=0)))
+    @test !isnothing(RustyObjectStore.invalid_config_message(config(; multipart_put_part_size=0)))
+    @test !isnothing(RustyObjectStore.invalid_config_message(config(; adaptive_concurrency_min=1024)))
+end